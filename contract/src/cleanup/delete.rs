@@ -19,20 +19,36 @@ impl Contract {
 
     /// Clears storage by removing account details in batches.
     ///
-    /// This function will only work when the contract is frozen. It iterates
+    /// This function will only work when the contract is frozen. Any tokens left in
+    /// drop budgets are returned to the drop creators, or burned if their account
+    /// is gone or has offboarded, before the drops are cleared. It then iterates
     /// over the `account_details_by_id` map, processing up to 1000 accounts at
     /// a time. For each account, it removes the account from the outer map and
     /// clears any associated data in the inner maps (`drops_claimed` and
-    /// `drops_created`).
+    /// `drops_created`). Accounts that haven't offboarded would lose their
+    /// remaining tokens, so they are skipped unless `force` is set, in which case
+    /// their tokens are burned.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of accounts to clear. Defaults to 1000.
+    /// * `refund_account` - The account that receives the NEAR freed up from storage.
+    /// * `force` - Whether to also clear accounts that haven't offboarded yet.
     ///
     /// # Returns
     ///
-    /// Returns the number of accounts left to clear after this operation.
+    /// Returns the number of accounts left on the contract after this operation, including the
+    /// ones that were skipped.
     ///
     /// # Panics
     ///
     /// Panics if the contract is not frozen or if the caller is not an admin.
-    pub fn clear_storage(&mut self, limit: Option<u32>, refund_account: AccountId) -> u64 {
+    pub fn clear_storage(
+        &mut self,
+        limit: Option<u32>,
+        refund_account: AccountId,
+        force: Option<bool>,
+    ) -> u64 {
         // Ensure that only an admin can perform this operation.
         self.assert_contract_account();
         let storage_initial = env::storage_usage();
@@ -46,6 +62,34 @@ impl Contract {
         if !self.drop_by_id.is_empty() {
             let before = env::storage_usage();
             let num_drops = self.drop_by_id.len();
+
+            // Give each drop's remaining budget back to its creator so they can still offboard
+            // the tokens. Creators that are gone or have already offboarded can't, so it's burned.
+            let budgets: Vec<(AccountId, NearToken)> = self
+                .drop_by_id
+                .iter()
+                .filter_map(|(drop_id, drop)| {
+                    drop.get_budget()
+                        .filter(|budget| !budget.is_zero())
+                        .map(|budget| (parse_drop_id(drop_id), budget))
+                })
+                .collect();
+            for (drop_creator, budget) in budgets {
+                let can_offboard = self
+                    .account_details_by_id
+                    .get(&drop_creator)
+                    .is_some_and(|details| details.offboarded_to.is_none());
+                if can_offboard {
+                    self.internal_ft_deposit(&drop_creator, budget, false);
+                } else {
+                    self.internal_ft_burn_supply(
+                        &drop_creator,
+                        budget,
+                        Some("Drop budget cleared".to_string()),
+                    );
+                }
+            }
+
             self.drop_by_id.clear();
             self.drop_by_id.flush();
             near_sdk::log!(
@@ -69,6 +113,66 @@ impl Contract {
                 env::storage_usage()
             );
         }
+        if !self.ticket_stats_by_id.is_empty() {
+            let before = env::storage_usage();
+            let num_tickets = self.ticket_stats_by_id.len();
+            self.ticket_stats_by_id.clear();
+            self.ticket_stats_by_id.flush();
+            near_sdk::log!(
+                "Cleared {} ticket stats. {} bytes cleared. Initial {} Final {}",
+                num_tickets,
+                before - env::storage_usage(),
+                before,
+                env::storage_usage()
+            );
+        }
+        if !self.reserved_account_names.is_empty() {
+            let before = env::storage_usage();
+            let num_names = self.reserved_account_names.len();
+            self.reserved_account_names.clear();
+            self.reserved_account_names.flush();
+            near_sdk::log!(
+                "Cleared {} reserved account names. {} bytes cleared. Initial {} Final {}",
+                num_names,
+                before - env::storage_usage(),
+                before,
+                env::storage_usage()
+            );
+        }
+        if !self.vendor_info_by_id.is_empty() {
+            let before = env::storage_usage();
+            let num_vendors = self.vendor_info_by_id.len();
+            for (_, mut vendor_info) in self.vendor_info_by_id.drain() {
+                vendor_info.items.clear();
+                vendor_info.sales_by_item.clear();
+            }
+            self.vendor_info_by_id.flush();
+            near_sdk::log!(
+                "Cleared {} vendor catalogs. {} bytes cleared. Initial {} Final {}",
+                num_vendors,
+                before - env::storage_usage(),
+                before,
+                env::storage_usage()
+            );
+        }
+        if !self.wallet_contracts.is_empty() {
+            let before = env::storage_usage();
+            let num_wallets = self.wallet_contracts.len();
+            for version in self.wallet_contracts.keys() {
+                self.wallet_code_by_version.remove(version);
+            }
+            self.wallet_code_by_version.flush();
+            self.wallet_contracts.clear();
+            self.wallet_contracts.flush();
+            self.default_wallet_version = None;
+            near_sdk::log!(
+                "Cleared {} wallet contracts. {} bytes cleared. Initial {} Final {}",
+                num_wallets,
+                before - env::storage_usage(),
+                before,
+                env::storage_usage()
+            );
+        }
         if self.agenda.is_empty() {
             let before = env::storage_usage();
             self.agenda = String::new();
@@ -104,6 +208,32 @@ impl Contract {
                 env::storage_usage()
             );
         }
+        if !self.check_ins_by_location.is_empty() {
+            let before = env::storage_usage();
+            let num_locations = self.check_ins_by_location.len();
+            self.check_ins_by_location.clear();
+            self.check_ins_by_location.flush();
+            near_sdk::log!(
+                "Cleared {} check-in locations. {} bytes cleared. Initial {} Final {}",
+                num_locations,
+                before - env::storage_usage(),
+                before,
+                env::storage_usage()
+            );
+        }
+        if !self.attendance_by_session.is_empty() {
+            let before = env::storage_usage();
+            let num_sessions = self.attendance_by_session.len();
+            self.attendance_by_session.clear();
+            self.attendance_by_session.flush();
+            near_sdk::log!(
+                "Cleared {} session attendance counts. {} bytes cleared. Initial {} Final {}",
+                num_sessions,
+                before - env::storage_usage(),
+                before,
+                env::storage_usage()
+            );
+        }
         if self.token_leaderboard.is_empty() {
             let before = env::storage_usage();
             self.token_leaderboard = Vec::new();
@@ -135,32 +265,54 @@ impl Contract {
             );
         }
 
-        // Get the total number of accounts to clear.
-        let total = self.account_details_by_id.len();
-
         // Define the maximum number of accounts to process in one batch.
         let batch_size = limit.unwrap_or(1000);
+        let force = force.unwrap_or(false);
 
         // Collect the keys of the accounts to be processed in this batch (immutable borrow).
-        let accounts_to_process: Vec<_> = self
+        // Draining the map would remove every account, not just the batch.
+        let accounts_to_process: Vec<AccountId> = self
             .account_details_by_id
-            .drain()
+            .iter()
+            .filter(|(_, account_details)| force || account_details.offboarded_to.is_some())
+            .map(|(account_id, _)| account_id.clone())
             .take(batch_size as usize)
             .collect();
 
         // Initialize a counter to track the number of accounts processed in this batch.
         let mut processed = 0;
+        let mut not_offboarded = 0;
 
         // Now perform the removal in a separate mutable borrow (mutable borrow starts here).
-        for (_account_id, mut account_details) in accounts_to_process {
+        for account_id in accounts_to_process {
+            // Burn whatever tokens are left so they don't count towards the supply anymore
+            let balance = self.ft_balance_of(account_id.clone());
+            if !balance.is_zero() {
+                self.internal_ft_burn(&account_id, balance, Some("Account cleared".to_string()));
+            }
+
+            let mut account_details = self.account_details_by_id.remove(&account_id).unwrap();
             // Clear the inner data structures associated with this account.
             account_details.drops_claimed.clear();
             account_details.drops_created.clear();
+            account_details.multichain_mints.clear();
+            account_details.metadata_grants_received.clear();
+            account_details.metadata_grants_given.clear();
+            account_details.ft_allowances.clear();
+
+            if account_details.offboarded_to.is_none() {
+                not_offboarded += 1;
+            }
 
             // Increment the counter for each processed account.
             processed += 1;
         }
-        near_sdk::log!("Cleared {} accounts.", processed);
+        self.account_details_by_id.flush();
+        near_sdk::log!(
+            "Cleared {} accounts. {} had not offboarded.",
+            processed,
+            not_offboarded
+        );
 
         // Calculate the storage usage after the removals.
         let storage_used = storage_initial - env::storage_usage();
@@ -172,8 +324,7 @@ impl Contract {
         );
         on_storage_cleared(refund_account, storage_used);
 
-        // Calculate and return the number of accounts left to clear.
-
-        (total - processed) as u64
+        // Return the number of accounts left to clear.
+        self.account_details_by_id.len() as u64
     }
 }
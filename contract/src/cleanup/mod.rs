@@ -1,3 +1,6 @@
 pub mod delete;
 pub mod helpers;
+pub mod models;
+pub mod offboard;
 
+pub use models::*;
@@ -0,0 +1,44 @@
+use crate::*;
+
+/// Rate used to convert event tokens into another asset. The amount paid out is
+/// `balance * numerator / denominator`.
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub struct ExchangeRate {
+    pub numerator: U128,
+    pub denominator: U128,
+}
+
+impl ExchangeRate {
+    pub fn assert_valid(&self) {
+        require!(
+            self.denominator.0 > 0,
+            "Exchange rate denominator must be greater than zero"
+        );
+    }
+
+    pub fn convert(&self, amount: NearToken) -> u128 {
+        amount
+            .as_yoctonear()
+            .checked_mul(self.numerator.0)
+            .expect("Exchange rate overflow")
+            / self.denominator.0
+    }
+}
+
+/// What happens to an attendee's event tokens when they offboard.
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub enum ExchangePolicy {
+    // The tokens are burned and nothing is paid out
+    Burn,
+    // The tokens are burned and $NEAR is sent to the destination account
+    Near {
+        rate: ExchangeRate,
+    },
+    // The tokens are burned and a NEP-141 token is sent to the destination account
+    FungibleToken {
+        token_contract: AccountId,
+        rate: ExchangeRate,
+    },
+}
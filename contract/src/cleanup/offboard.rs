@@ -0,0 +1,197 @@
+use near_sdk::{ext_contract, Gas, Promise, PromiseError};
+
+use crate::*;
+
+const GAS_FOR_EXCHANGE_FT_TRANSFER: Gas = Gas::from_tgas(10); // 10 TGas = 10^13 gas units
+const GAS_FOR_RESOLVE_OFFBOARD: Gas = Gas::from_tgas(10); // 10 TGas = 10^13 gas units
+
+#[allow(dead_code)]
+#[ext_contract(ext_ft)]
+pub trait FungibleTokenCore {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+#[near]
+impl Contract {
+    /// Allows an admin to set what happens to attendees' event tokens when they offboard.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin or if the exchange rate is invalid.
+    pub fn set_exchange_policy(&mut self, policy: ExchangePolicy) {
+        self.assert_admin();
+        match &policy {
+            ExchangePolicy::Burn => {}
+            ExchangePolicy::Near { rate } | ExchangePolicy::FungibleToken { rate, .. } => {
+                rate.assert_valid()
+            }
+        }
+        self.exchange_policy = policy;
+    }
+
+    /// Query for what happens to attendees' event tokens when they offboard.
+    pub fn get_exchange_policy(&self) -> ExchangePolicy {
+        self.exchange_policy.clone()
+    }
+
+    /// Moves the caller's NFTs out of the contract and burns their event tokens once the
+    /// conference is over. Depending on the exchange policy, the burned tokens are converted into
+    /// $NEAR or another token that is sent to the destination account.
+    ///
+    /// # Arguments
+    ///
+    /// * `destination_account` - The account that receives the NFTs and exchanged tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the contract isn't frozen, if the caller has no account, if they have already
+    /// offboarded or if the destination is the caller.
+    pub fn offboard(&mut self, destination_account: AccountId) {
+        require!(
+            self.is_contract_frozen,
+            "Offboarding is only available once the conference is over"
+        );
+        let account_id = self.caller_id_by_signing_pk();
+        require!(
+            account_id != destination_account,
+            "Destination must be a different account"
+        );
+
+        let account_details = self
+            .account_details_by_id
+            .get_mut(&account_id)
+            .expect("Account not found");
+        require!(
+            account_details.offboarded_to.is_none(),
+            "Account has already offboarded"
+        );
+        account_details.offboarded_to = Some(destination_account.clone());
+        let tokens_burned = account_details.ft_balance;
+
+        // Move every NFT out to the destination account
+        let token_ids: Vec<TokenId> = self
+            .nft_tokens_per_owner
+            .get(&account_id)
+            .map(|tokens| tokens.iter().cloned().collect())
+            .unwrap_or_default();
+        for token_id in token_ids.iter() {
+            self.internal_transfer(
+                &account_id,
+                &destination_account,
+                token_id,
+                None,
+                Some("Offboarded".to_string()),
+            );
+        }
+
+        // Burn the event tokens and pay out whatever they're exchanged for
+        let mut amount_exchanged = None;
+        if !tokens_burned.is_zero() {
+            self.internal_ft_burn(&account_id, tokens_burned, Some("Offboarded".to_string()));
+
+            let payout = match &self.exchange_policy {
+                ExchangePolicy::Burn => None,
+                ExchangePolicy::Near { rate } => {
+                    let amount = rate.convert(tokens_burned);
+                    amount_exchanged = Some(U128(amount));
+                    (amount > 0).then(|| {
+                        Promise::new(destination_account.clone())
+                            .transfer(NearToken::from_yoctonear(amount))
+                    })
+                }
+                ExchangePolicy::FungibleToken {
+                    token_contract,
+                    rate,
+                } => {
+                    let amount = rate.convert(tokens_burned);
+                    amount_exchanged = Some(U128(amount));
+                    (amount > 0).then(|| {
+                        ext_ft::ext(token_contract.clone())
+                            .with_attached_deposit(NearToken::from_yoctonear(1))
+                            .with_static_gas(GAS_FOR_EXCHANGE_FT_TRANSFER)
+                            .ft_transfer(
+                                destination_account.clone(),
+                                U128(amount),
+                                Some("Offboarded".to_string()),
+                            )
+                    })
+                }
+            };
+
+            // Give the tokens back if the payout fails so that the attendee can try again
+            if let Some(payout) = payout {
+                payout.then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_RESOLVE_OFFBOARD)
+                        .with_unused_gas_weight(0)
+                        .resolve_offboard(account_id.clone(), tokens_burned),
+                );
+            }
+        }
+
+        env::log_str(
+            &EventLog {
+                standard: KEYPOM_STANDARD_NAME.to_string(),
+                version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
+                event: EventLogVariant::KeypomOffboard(KeypomOffboardLog {
+                    account_id: account_id.to_string(),
+                    destination_id: destination_account.to_string(),
+                    token_ids,
+                    tokens_burned: U128(tokens_burned.as_yoctonear()),
+                    amount_exchanged,
+                }),
+            }
+            .to_string(),
+        );
+    }
+
+    /// Resolves the payout of an offboarding. If the payout failed, the burned tokens are minted
+    /// back to the account and it is no longer marked as offboarded.
+    ///
+    /// # Returns
+    ///
+    /// Returns whether the payout succeeded.
+    #[private]
+    pub fn resolve_offboard(
+        &mut self,
+        account_id: AccountId,
+        tokens_burned: NearToken,
+        #[callback_result] result: Result<(), PromiseError>,
+    ) -> bool {
+        if result.is_ok() {
+            return true;
+        }
+
+        // The account may have been cleared while the payout was in flight
+        let Some(account_details) = self.account_details_by_id.get_mut(&account_id) else {
+            return false;
+        };
+        account_details.offboarded_to = None;
+
+        self.internal_ft_deposit(&account_id, tokens_burned, false);
+        self.ft_total_supply = self
+            .ft_total_supply
+            .checked_add(tokens_burned)
+            .expect("NearToken overflow");
+
+        near_sdk::log!(
+            "Offboarding payout failed for {}. Returning {} tokens",
+            account_id,
+            tokens_burned.as_yoctonear()
+        );
+        env::log_str(
+            &EventLog {
+                standard: FT_STANDARD_NAME.to_string(),
+                version: FT_METADATA_SPEC.to_string(),
+                event: EventLogVariant::FtMint(FtMintLog {
+                    owner_id: account_id.to_string(),
+                    amount: tokens_burned.as_yoctonear().to_string(),
+                    memo: Some("Offboarding payout failed".to_string()),
+                }),
+            }
+            .to_string(),
+        );
+
+        false
+    }
+}
@@ -0,0 +1,89 @@
+use crate::*;
+
+/// Maximum length of the metadata an attendee can share with a sponsor, since the contract pays
+/// for its storage.
+pub const MAX_ENCRYPTED_METADATA_LEN: usize = 2048;
+
+#[near]
+impl Contract {
+    /// Allows an attendee to share their metadata with a sponsor, for example their email or
+    /// company at a booth. Granting access to a sponsor that already has access replaces the
+    /// previously shared metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `sponsor_id` - The sponsor to share the metadata with.
+    /// * `encrypted_metadata` - The attendee's metadata re-encrypted for the sponsor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the attendee or sponsor is not found, if the account being granted access is not
+    /// a sponsor, or if the metadata is longer than `MAX_ENCRYPTED_METADATA_LEN`.
+    pub fn grant_metadata_access(&mut self, sponsor_id: AccountId, encrypted_metadata: String) {
+        self.assert_no_freeze();
+        let attendee_id = self.caller_id_by_signing_pk();
+        require!(attendee_id != sponsor_id, "Cannot grant access to yourself");
+        require!(
+            encrypted_metadata.len() <= MAX_ENCRYPTED_METADATA_LEN,
+            "Encrypted metadata is too long"
+        );
+
+        let sponsor_details = self
+            .account_details_by_id
+            .get_mut(&sponsor_id)
+            .expect("Sponsor not found");
+        require!(
+            sponsor_details
+                .account_status
+                .as_ref()
+                .is_some_and(|status| status.is_sponsor()),
+            "Metadata can only be shared with sponsors"
+        );
+        sponsor_details.metadata_grants_received.insert(
+            attendee_id.clone(),
+            MetadataGrant {
+                encrypted_metadata,
+                granted_at: env::block_timestamp(),
+            },
+        );
+
+        self.account_details_by_id
+            .get_mut(&attendee_id)
+            .expect("Attendee not found")
+            .metadata_grants_given
+            .insert(sponsor_id);
+
+        self.total_transactions += 1;
+    }
+
+    /// Allows an attendee to revoke a sponsor's access to their metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `sponsor_id` - The sponsor whose access should be revoked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the attendee hasn't shared their metadata with the sponsor.
+    pub fn revoke_metadata_access(&mut self, sponsor_id: AccountId) {
+        self.assert_no_freeze();
+        let attendee_id = self.caller_id_by_signing_pk();
+
+        require!(
+            self.account_details_by_id
+                .get_mut(&attendee_id)
+                .expect("Attendee not found")
+                .metadata_grants_given
+                .remove(&sponsor_id),
+            "No metadata access granted to this sponsor"
+        );
+
+        if let Some(sponsor_details) = self.account_details_by_id.get_mut(&sponsor_id) {
+            sponsor_details
+                .metadata_grants_received
+                .remove(&attendee_id);
+        }
+
+        self.total_transactions += 1;
+    }
+}
@@ -0,0 +1,5 @@
+pub mod grants;
+pub mod models;
+pub mod views;
+
+pub use models::*;
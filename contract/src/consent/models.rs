@@ -0,0 +1,18 @@
+use crate::*;
+
+/// Attendee metadata that has been shared with a sponsor. The metadata is re-encrypted off-chain
+/// so that only the sponsor can read it.
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub struct MetadataGrant {
+    pub encrypted_metadata: String,
+    pub granted_at: u64,
+}
+
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub struct ExtMetadataGrant {
+    pub attendee_id: AccountId,
+    pub encrypted_metadata: String,
+    pub granted_at: u64,
+}
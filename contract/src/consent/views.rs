@@ -0,0 +1,80 @@
+use crate::*;
+
+#[near]
+impl Contract {
+    /// Query for the metadata an attendee has shared with a sponsor.
+    ///
+    /// # Arguments
+    ///
+    /// * `attendee_id` - The attendee that shared their metadata.
+    /// * `sponsor_id` - The sponsor the metadata was shared with.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `MetadataGrant` if access was granted, otherwise `None`.
+    pub fn get_metadata_grant(
+        &self,
+        attendee_id: AccountId,
+        sponsor_id: AccountId,
+    ) -> Option<MetadataGrant> {
+        self.account_details_by_id
+            .get(&sponsor_id)
+            .and_then(|details| details.metadata_grants_received.get(&attendee_id))
+            .cloned()
+    }
+
+    /// Query for a paginated list of the metadata grants a sponsor has received.
+    ///
+    /// # Arguments
+    ///
+    /// * `sponsor_id` - The sponsor that received the grants.
+    /// * `from_index` - The starting index for pagination.
+    /// * `limit` - The maximum number of grants to retrieve.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `ExtMetadataGrant` containing the attendee and their shared metadata.
+    pub fn get_metadata_grants_for_sponsor(
+        &self,
+        sponsor_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<ExtMetadataGrant> {
+        // Where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        self.account_details_by_id
+            .get(&sponsor_id)
+            .map(|details| {
+                details
+                    .metadata_grants_received
+                    .iter()
+                    .skip(start as usize)
+                    // If we didn't specify a limit, use 50
+                    .take(limit.unwrap_or(50) as usize)
+                    .map(|(attendee_id, grant)| ExtMetadataGrant {
+                        attendee_id: attendee_id.clone(),
+                        encrypted_metadata: grant.encrypted_metadata.clone(),
+                        granted_at: grant.granted_at,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Query for the sponsors an attendee has shared their metadata with.
+    ///
+    /// # Arguments
+    ///
+    /// * `attendee_id` - The attendee that shared their metadata.
+    ///
+    /// # Returns
+    ///
+    /// A vector of sponsor account IDs.
+    pub fn get_metadata_grants_by_attendee(&self, attendee_id: AccountId) -> Vec<AccountId> {
+        self.account_details_by_id
+            .get(&attendee_id)
+            .map(|details| details.metadata_grants_given.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
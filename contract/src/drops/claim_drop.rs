@@ -8,24 +8,37 @@ impl Contract {
     ///
     /// * `drop_id` - The ID of the drop to be claimed.
     /// * `scavenger_id` - Optional scavenger ID to claim.
+    /// * `signature` - Signature of the claim message by the drop or scavenger key.
+    /// * `expires_at` - Block timestamp after which the signature is no longer valid. Can only be
+    /// left out for drops that accept legacy claim signatures.
     ///
     /// # Panics
     ///
-    /// Panics if the drop is not found or if the user is not registered.
+    /// Panics if the drop is not found, if the user is not registered, or if the signature is
+    /// invalid or expired.
     pub fn claim_drop(
         &mut self,
         drop_id: String,
         scavenger_id: Option<PublicKey>,
         signature: Base64VecU8,
+        expires_at: Option<U64>,
     ) -> ExtClaimedDrop {
         self.assert_no_freeze();
         let receiver_id = self.caller_id_by_signing_pk();
-        self.assert_valid_signature(&drop_id, &receiver_id, &signature, scavenger_id.clone());
+        self.assert_valid_signature(
+            &drop_id,
+            &receiver_id,
+            &signature,
+            scavenger_id.clone(),
+            expires_at.map(u64::from),
+        );
 
         // Handle the claim logic based on the drop type
         let claim_log = self.handle_claim_drop(&drop_id, &receiver_id, scavenger_id);
 
-        let reward = match claim_log.reward {
+        // Hunts that only hit a milestone record the milestone reward instead of a piece
+        let claim_reward = claim_log.reward.as_ref();
+        let reward = match claim_reward.or(claim_log.milestone_reward.as_ref()) {
             Some(DropClaimReward::Token(amount)) => format!("{}", amount.0),
             Some(DropClaimReward::Nft) => "NFT".to_string(),
             Some(DropClaimReward::Multichain) => "Multichain POAP".to_string(),
@@ -75,8 +88,13 @@ impl Contract {
             reward: None,
             pieces_found: None,
             pieces_required: None,
+            milestone_reached: None,
+            milestone_reward: None,
         };
 
+        // Make sure the drop can currently be claimed
+        drop_data.get_config().assert_claimable();
+
         // Increment the number of claims for the drop
         match &mut drop_data {
             DropData::Token(data) => data.num_claimed += 1,
@@ -84,42 +102,52 @@ impl Contract {
             DropData::Multichain(data) => data.num_claimed += 1,
         }
 
-        // Extract scavenger hunt information if available
-        let scavenger_hunt = match &drop_data {
-            DropData::Token(data) => data.scavenger_hunt.as_ref(),
-            DropData::Nft(data) => data.scavenger_hunt.as_ref(),
-            DropData::Multichain(data) => data.scavenger_hunt.as_ref(),
-        };
-
-        if let Some(required_scavenger_ids) = scavenger_hunt {
+        if let Some(required_scavenger_ids) = drop_data.get_scavenger_data() {
             let found_scavenger_id = found_scavenger_id.expect("This drop requires a scavenger ID");
 
             // Handle scavenger hunt logic
             let hunt_complete = self.handle_scavenger_hunt(
                 receiver_id,
-                required_scavenger_ids,
+                &required_scavenger_ids,
                 found_scavenger_id,
                 drop_id,
+                drop_data.get_config().ordered_scavenger_hunt,
                 &mut event_log,
             );
 
-            if hunt_complete {
-                // Process the reward based on drop type
-                let reward = match drop_data {
-                    DropData::Token(ref data) => {
-                        self.internal_deposit_ft_transfer(data, drop_id, receiver_id);
-                        DropClaimReward::Token(data.token_amount)
-                    }
-                    DropData::Nft(ref data) => {
-                        self.internal_nft_mint(data.nft_series_id, receiver_id.clone());
-                        DropClaimReward::Nft
-                    }
-                    DropData::Multichain(ref data) => {
-                        self.handle_multichain_mint(data);
-                        DropClaimReward::Multichain
+            // Pay out any milestone reward for the number of pieces found so far. Milestones have
+            // their own payouts, so one that has run out is skipped and the piece is still recorded.
+            let pieces_found = event_log.pieces_found.unwrap_or_default();
+            if let Some(milestone) = drop_data.get_scavenger_milestone(pieces_found) {
+                let has_payouts_left = drop_data.get_config().has_claims_left(milestone.num_paid)
+                    && drop_data
+                        .get_budget()
+                        .is_none_or(|budget| budget.as_yoctonear() >= milestone.token_amount.0);
+                if has_payouts_left {
+                    self.internal_deposit_ft_transfer(
+                        &mut drop_data,
+                        drop_id,
+                        receiver_id,
+                        milestone.token_amount,
+                    );
+                    if let Some(milestone) = drop_data.get_scavenger_milestone_mut(pieces_found) {
+                        milestone.num_paid += 1;
                     }
-                };
-                event_log.reward = Some(reward);
+                    event_log.milestone_reached = Some(milestone.pieces_found);
+                    event_log.milestone_reward =
+                        Some(DropClaimReward::Token(milestone.token_amount));
+                } else {
+                    near_sdk::log!(
+                        "Milestone for {} pieces on drop {} has no payouts left. Skipping its reward",
+                        milestone.pieces_found,
+                        drop_id
+                    );
+                }
+            }
+
+            if hunt_complete {
+                event_log.reward =
+                    Some(self.internal_claim_reward(&mut drop_data, drop_id, receiver_id));
             }
         } else {
             // Use entry API to access and modify account details
@@ -137,22 +165,8 @@ impl Contract {
                 "Drop already claimed"
             );
 
-            // Process the reward based on drop type
-            let reward = match drop_data {
-                DropData::Token(ref data) => {
-                    self.internal_deposit_ft_transfer(data, drop_id, receiver_id);
-                    DropClaimReward::Token(data.token_amount)
-                }
-                DropData::Nft(ref data) => {
-                    self.internal_nft_mint(data.nft_series_id, receiver_id.clone());
-                    DropClaimReward::Nft
-                }
-                DropData::Multichain(ref data) => {
-                    self.handle_multichain_mint(data);
-                    DropClaimReward::Multichain
-                }
-            };
-            event_log.reward = Some(reward);
+            event_log.reward =
+                Some(self.internal_claim_reward(&mut drop_data, drop_id, receiver_id));
         }
 
         self.drop_by_id.insert(drop_id.to_string(), drop_data);
@@ -160,6 +174,47 @@ impl Contract {
         event_log
     }
 
+    /// Hands out the drop's reward to the receiver based on the drop type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the drop has already handed out its maximum number of rewards.
+    fn internal_claim_reward(
+        &mut self,
+        drop_data: &mut DropData,
+        drop_id: &DropId,
+        receiver_id: &AccountId,
+    ) -> DropClaimReward {
+        require!(
+            drop_data
+                .get_config()
+                .has_claims_left(drop_data.get_num_rewarded()),
+            "Drop has reached its maximum number of claims"
+        );
+
+        let reward = match drop_data {
+            DropData::Token(data) => {
+                data.num_rewarded += 1;
+                DropClaimReward::Token(data.token_amount)
+            }
+            DropData::Nft(data) => {
+                data.num_rewarded += 1;
+                self.internal_nft_mint(data.nft_series_id, receiver_id.clone());
+                DropClaimReward::Nft
+            }
+            DropData::Multichain(data) => {
+                data.num_rewarded += 1;
+                self.handle_multichain_mint(data, drop_id, receiver_id);
+                DropClaimReward::Multichain
+            }
+        };
+
+        if let DropClaimReward::Token(amount) = reward {
+            self.internal_deposit_ft_transfer(drop_data, drop_id, receiver_id, amount);
+        }
+        reward
+    }
+
     /// Handles the scavenger hunt logic common to all drop types.
     ///
     /// # Arguments
@@ -168,6 +223,7 @@ impl Contract {
     /// * `found_scavenger_id` - The scavenger ID found by the user.
     /// * `claimed_drops` - The map of claimed drops for the receiver.
     /// * `drop_id` - The ID of the drop.
+    /// * `ordered` - Whether the pieces need to be found in order of their IDs.
     /// * `event_log` - The event log to update with scavenger hunt info.
     ///
     /// # Returns
@@ -179,6 +235,7 @@ impl Contract {
         required_scavenger_ids: &[ScavengerHuntData],
         found_scavenger_id: PublicKey,
         drop_id: &DropId,
+        ordered: bool,
         event_log: &mut KeypomDropClaimLog,
     ) -> bool {
         // Check if the found_scavenger_id is valid and hasn't been claimed yet
@@ -205,6 +262,18 @@ impl Contract {
             .contains(&found_scavenger_id);
         require!(!already_claimed, "Scavenger piece already claimed");
 
+        // Ordered hunts only accept the lowest numbered piece that hasn't been found yet
+        if ordered {
+            let next_piece = next_scavenger_piece(
+                required_scavenger_ids,
+                claimed_drop.as_deref().unwrap_or_default(),
+            );
+            require!(
+                next_piece.is_some_and(|piece| piece.key == found_scavenger_id),
+                "Scavenger pieces must be found in order"
+            );
+        }
+
         // Add the valid scavenger_id to the claimed_drop
         claimed_drop
             .as_mut()
@@ -224,79 +293,87 @@ impl Contract {
         found_scavenger_ids == required_scavenger_ids_count
     }
 
-    /// Decrements the tokens from the sponsor's balance for a token drop.
+    /// Pays out tokens from a drop to the receiver, either the drop's own reward or a scavenger
+    /// milestone reward.
     ///
-    /// If the drop creator is an admin, the tokens are minted internally.
-    /// Otherwise, an FT transfer is performed from the drop creator to the user.
+    /// If the drop was created by an admin, the tokens are minted internally.
+    /// Otherwise, the tokens are drawn from the budget the sponsor set aside when creating the drop,
+    /// or from the sponsor's balance for drops created before budgets were reserved.
     ///
     /// # Panics
     ///
-    /// Panics if the sponsor doesn't have enough tokens to cover the amount to be claimed.
+    /// Panics if the drop's budget doesn't have enough tokens left to cover the amount to be claimed.
     ///
     /// # Arguments
     ///
-    /// * `drop` - The internal token drop data containing the creator ID and the amount to be claimed.
+    /// * `drop` - The internal drop data containing the budget.
+    /// * `amount` - The amount of tokens to be claimed.
     fn internal_deposit_ft_transfer(
         &mut self,
-        drop: &TokenDropData,
+        drop: &mut DropData,
         drop_id: &DropId,
         receiver_id: &AccountId,
+        amount: U128,
     ) {
         let drop_creator = parse_drop_id(drop_id);
-        env::log_str(
-            format!(
-                "Internal deposit ft transfer drop creator: {:?}",
-                drop_creator
-            )
-            .as_str(),
-        );
-        let account_details = self
-            .account_details_by_id
-            .get(&drop_creator)
-            .expect("Drop creator not found in map");
-        let creator_status = account_details
-            .account_status
-            .as_ref()
-            .expect("Drop creator not found");
-
-        let amount_to_claim = drop.token_amount.0;
-
-        if creator_status.is_admin() {
-            env::log_str(format!("Creator is admin: {}", drop_creator).as_str());
-            // Mint tokens internally if the creator is an admin
-            self.internal_deposit_ft_mint(
-                receiver_id,
-                NearToken::from_yoctonear(amount_to_claim),
-                Some(drop_id.clone()),
-                true,
-            );
-        } else if creator_status.is_sponsor() {
+        let amount_to_claim = NearToken::from_yoctonear(amount.0);
+
+        if let Some(budget) = drop.get_budget() {
             env::log_str(format!("Creator is sponsor {:?}", drop_creator).as_str());
-            // Get the current token balance of the creator
-            let cur_creator_tokens = account_details.ft_balance;
 
-            // Check if the creator has enough tokens to cover the amount to be claimed
-            require!(
-                cur_creator_tokens.ge(&NearToken::from_yoctonear(amount_to_claim)),
-                "The creator does not have enough tokens to cover the amount to be claimed."
+            // Check if the drop has enough tokens left to cover the amount to be claimed
+            let remaining_budget = budget.checked_sub(amount_to_claim).expect(
+                "The drop does not have enough tokens left to cover the amount to be claimed.",
             );
+            drop.set_budget(remaining_budget);
 
-            // Perform FT transfer from the drop creator to the receiver
-            self.internal_ft_transfer(
-                &drop_creator,
-                receiver_id,
-                NearToken::from_yoctonear(amount_to_claim),
-                true,
-            );
+            // Release the tokens from the budget to the receiver
+            self.internal_ft_deposit(receiver_id, amount_to_claim, true);
+            self.internal_record_ft_transfer(&drop_creator, receiver_id, amount_to_claim, None);
+        } else {
+            let creator_status = self
+                .account_details_by_id
+                .get(&drop_creator)
+                .expect("Drop creator not found in map")
+                .account_status
+                .as_ref()
+                .expect("Drop creator not found");
+
+            if creator_status.is_admin() {
+                env::log_str(format!("Creator is admin: {}", drop_creator).as_str());
+                // Mint tokens internally if the creator is an admin
+                self.internal_deposit_ft_mint(
+                    receiver_id,
+                    amount_to_claim,
+                    Some(drop_id.clone()),
+                    true,
+                );
+            } else {
+                // Sponsor drops created before budgets were reserved pay out of their balance
+                require!(creator_status.is_sponsor(), "Drop has no token budget");
+                require!(
+                    self.ft_balance_of(drop_creator.clone()) >= amount_to_claim,
+                    "The creator does not have enough tokens to cover the amount to be claimed."
+                );
+                self.internal_ft_transfer(&drop_creator, receiver_id, amount_to_claim, true, None);
+            }
         }
     }
 
+    /// Ensures the claim was signed by the drop or scavenger key. Replay-resistant signatures
+    /// bump the receiver's claim nonce so they can't be used again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the signature is invalid, if it has expired, or if it uses the legacy format on
+    /// a drop that doesn't accept it.
     pub(crate) fn assert_valid_signature(
-        &self,
+        &mut self,
         drop_id: &DropId,
         receiver_id: &AccountId,
         signature: &Base64VecU8,
         scavenger_id: Option<PublicKey>,
+        expires_at: Option<u64>,
     ) {
         // Determine the expected public key
         let drop_data = self.drop_by_id.get(drop_id).expect("Drop not found");
@@ -310,9 +387,43 @@ impl Contract {
                 DropData::Multichain(data) => data.key.clone(),
             }
         };
+
+        let claim_params = match expires_at {
+            Some(expires_at) => {
+                require!(
+                    env::block_timestamp() <= expires_at,
+                    "Claim signature has expired"
+                );
+                Some(ClaimSignatureParams {
+                    drop_id: drop_id.clone(),
+                    nonce: self.get_claim_nonce(receiver_id.clone()),
+                    expires_at,
+                })
+            }
+            None => {
+                require!(
+                    drop_data.get_config().legacy_claim_signatures,
+                    "Drop requires a claim signature with an expiry"
+                );
+                None
+            }
+        };
+
         // Verify the signature
-        let is_valid_signature =
-            verify_signature(signature.clone(), receiver_id.clone(), expected_key.clone());
+        let is_valid_signature = verify_signature(
+            signature.clone(),
+            receiver_id.clone(),
+            expected_key.clone(),
+            claim_params,
+        );
         require!(is_valid_signature, "Invalid signature");
+
+        // Bump the nonce so the signature can't be replayed
+        if expires_at.is_some() {
+            self.account_details_by_id
+                .entry(receiver_id.clone())
+                .or_insert_with(|| AccountDetails::new(receiver_id))
+                .claim_nonce += 1;
+        }
     }
 }
\ No newline at end of file
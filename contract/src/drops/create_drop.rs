@@ -1,3 +1,6 @@
+// The generated cross-contract helpers take every argument of `create_multichain_drop`
+#![allow(clippy::too_many_arguments)]
+
 use crate::*;
 
 #[near]
@@ -8,6 +11,7 @@ impl Contract {
     ///
     /// * `drop_data` - The base drop data such as scavenger hunt IDs, name, image
     /// * `token_amount` - The amount of tokens that this drop contains
+    /// * `config` - Optional claim limits such as a maximum number of claims or a claim window
     ///
     /// # Panics
     ///
@@ -19,52 +23,22 @@ impl Contract {
         scavenger_hunt: Option<Vec<ScavengerHuntData>>,
         key: PublicKey,
         token_amount: U128,
+        config: Option<DropConfig>,
     ) -> String {
         self.assert_no_freeze();
         let drop_creator = self.assert_sponsor();
-
-        let account_details = self
-            .account_details_by_id
-            .entry(drop_creator.clone())
-            .or_insert_with(|| AccountDetails::new(&drop_creator));
-
-        // The drop ID will be a concatenation of the creator, delimiter, and the drop number
-        let drop_id = format!(
-            "{}{}{}",
-            drop_creator, DROP_DELIMITER, account_details.drop_nonce
+        let drop_id = self.internal_create_drop(
+            &drop_creator,
+            DropCreationArgs {
+                image,
+                name,
+                key,
+                scavenger_hunt,
+                scavenger_milestones: None,
+                config,
+                reward: DropCreationReward::Token { token_amount },
+            },
         );
-        require!(
-            self.drop_by_id
-                .insert(
-                    drop_id.clone(),
-                    DropData::Token(TokenDropData {
-                        key,
-                        name,
-                        image,
-                        num_claimed: 0,
-                        scavenger_hunt: scavenger_hunt.clone(),
-                        id: drop_id.clone(),
-                        token_amount
-                    })
-                )
-                .is_none(),
-            "Drop ID already exists"
-        );
-
-        // Add the drop ID to the creator's list of drop IDs
-        account_details.drops_created.insert(drop_id.clone());
-        account_details.drop_nonce += 1;
-
-        let drop_creation_log: EventLog = EventLog {
-            standard: KEYPOM_STANDARD_NAME.to_string(),
-            version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
-            event: EventLogVariant::KeypomDropCreation(KeypomDropCreationLog {
-                creator_id: drop_creator.to_string(),
-                drop_reward: DropClaimReward::Token(token_amount),
-                num_scavengers: scavenger_hunt.map(|scavenger_hunt| scavenger_hunt.len() as u16),
-            }),
-        };
-        env::log_str(&drop_creation_log.to_string());
 
         self.total_transactions += 1;
         drop_id
@@ -76,6 +50,7 @@ impl Contract {
     ///
     /// * `drop_data` - The base drop data such as scavenger hunt IDs, name, image
     /// * `nft_metadata` - The metadata for the NFTs that will be minted as part of this drop
+    /// * `config` - Optional claim limits such as a maximum number of claims or a claim window
     ///
     /// # Panics
     ///
@@ -87,69 +62,22 @@ impl Contract {
         key: PublicKey,
         scavenger_hunt: Option<Vec<ScavengerHuntData>>,
         nft_metadata: TokenMetadata,
+        config: Option<DropConfig>,
     ) -> String {
         let drop_creator = self.assert_sponsor();
-
-        let account_details = self
-            .account_details_by_id
-            .entry(drop_creator.clone())
-            .or_insert_with(|| AccountDetails::new(&drop_creator));
-
-        // The drop ID will be a concatenation of the creator, delimiter, and the drop number
-        let drop_id = format!(
-            "{}{}{}",
-            drop_creator, DROP_DELIMITER, account_details.drop_nonce
-        );
-
-        // Create the series
-        let series_id = self.series_by_id.len();
-        let tokens = IterableSet::new(StorageKeys::SeriesByIdInner {
-            account_id_hash: hash_string(&format!("{}{}", drop_creator, series_id)),
-        });
-        let series = Series {
-            metadata: nft_metadata.clone(),
-            royalty: None,
-            tokens,
-        };
-        require!(
-            self.series_by_id.insert(series_id, series).is_none(),
-            "Series ID already exists on the contract"
-        );
-
-        require!(
-            self.drop_by_id
-                .insert(
-                    drop_id.clone(),
-                    DropData::Nft(NFTDropData {
-                        name,
-                        key,
-                        image,
-                        num_claimed: 0,
-                        scavenger_hunt: scavenger_hunt.clone(),
-                        id: drop_id.clone(),
-                        nft_metadata,
-                        nft_series_id: series_id
-                    })
-                )
-                .is_none(),
-            "Drop ID already exists"
+        let drop_id = self.internal_create_drop(
+            &drop_creator,
+            DropCreationArgs {
+                image,
+                name,
+                key,
+                scavenger_hunt,
+                scavenger_milestones: None,
+                config,
+                reward: DropCreationReward::Nft { nft_metadata },
+            },
         );
 
-        // Add the drop ID to the creator's list of drop IDs
-        account_details.drops_created.insert(drop_id.clone());
-        account_details.drop_nonce += 1;
-
-        let drop_creation_log: EventLog = EventLog {
-            standard: KEYPOM_STANDARD_NAME.to_string(),
-            version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
-            event: EventLogVariant::KeypomDropCreation(KeypomDropCreationLog {
-                creator_id: drop_creator.to_string(),
-                drop_reward: DropClaimReward::Nft,
-                num_scavengers: scavenger_hunt.map(|scavs| scavs.len() as u16),
-            }),
-        };
-        env::log_str(&drop_creation_log.to_string());
-
         self.total_transactions += 1;
         drop_id
     }
@@ -161,12 +89,13 @@ impl Contract {
     ///
     /// * `series_id` - The series ID of the NFTs that will be minted on the external chain. You
     /// need to have previously called the create series method on the external chain.
-    /// * `contract_id` - The NFT contract ID that is deployed on the external chain.
+    /// * `contract_id` - The 0x prefixed address of the NFT contract deployed on the external chain.
     /// * `chain_id` - The ID of the external chain.
+    /// * `config` - Optional claim limits such as a maximum number of claims or a claim window
     ///
     /// # Panics
     ///
-    /// Panics if the account is not an admin.
+    /// Panics if the account is not an admin or if the contract ID is not an EVM address.
     pub fn create_multichain_drop(
         &mut self,
         image: String,
@@ -175,56 +104,69 @@ impl Contract {
         scavenger_hunt: Option<Vec<ScavengerHuntData>>,
         multichain_metadata: MultichainMetadata,
         nft_metadata: TokenMetadata,
+        config: Option<DropConfig>,
     ) -> String {
         let drop_creator = self.assert_sponsor();
+        let drop_id = self.internal_create_drop(
+            &drop_creator,
+            DropCreationArgs {
+                image,
+                name,
+                key,
+                scavenger_hunt,
+                scavenger_milestones: None,
+                config,
+                reward: DropCreationReward::Multichain {
+                    multichain_metadata,
+                    nft_metadata,
+                },
+            },
+        );
 
-        let account_details = self
-            .account_details_by_id
-            .entry(drop_creator.clone())
-            .or_insert_with(|| AccountDetails::new(&drop_creator));
+        self.total_transactions += 1;
+        drop_id
+    }
 
-        // The drop ID will be a concatenation of the creator, delimiter, and the drop number
-        let drop_id = format!(
-            "{}{}{}",
-            drop_creator, DROP_DELIMITER, account_details.drop_nonce
-        );
+    /// Allows a sponsor or admin to create a batch of token, NFT and multichain drops in a single
+    /// transaction. Drop IDs are allocated sequentially from the creator's drop nonce.
+    ///
+    /// # Arguments
+    ///
+    /// * `drops` - The arguments for each drop to be created.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the created drops in the same order as they were passed in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sponsor is not authorized or if too many drops are passed in.
+    pub fn create_drops(&mut self, drops: Vec<DropCreationArgs>) -> Vec<DropId> {
+        self.assert_no_freeze();
+        let drop_creator = self.assert_sponsor();
 
+        require!(!drops.is_empty(), "No drops passed in");
         require!(
-            self.drop_by_id
-                .insert(
-                    drop_id.clone(),
-                    DropData::Multichain(MultichainDropData {
-                        name,
-                        key,
-                        image,
-                        num_claimed: 0,
-                        nft_metadata,
-                        scavenger_hunt: scavenger_hunt.clone(),
-                        id: drop_id.clone(),
-                        mc_metadata: multichain_metadata
-                    })
-                )
-                .is_none(),
-            "Drop ID already exists"
+            drops.len() <= MAX_DROPS_PER_BATCH,
+            "Maximum number of drops exceeded"
         );
 
-        // Add the drop ID to the creator's list of drop IDs
-        account_details.drops_created.insert(drop_id.clone());
-        account_details.drop_nonce += 1;
+        let initial_storage_usage = env::storage_usage();
 
-        let drop_creation_log: EventLog = EventLog {
-            standard: KEYPOM_STANDARD_NAME.to_string(),
-            version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
-            event: EventLogVariant::KeypomDropCreation(KeypomDropCreationLog {
-                creator_id: drop_creator.to_string(),
-                drop_reward: DropClaimReward::Multichain,
-                num_scavengers: scavenger_hunt.map(|scavs| scavs.len() as u16),
-            }),
-        };
-        env::log_str(&drop_creation_log.to_string());
+        let drop_ids: Vec<DropId> = drops
+            .into_iter()
+            .map(|drop| self.internal_create_drop(&drop_creator, drop))
+            .collect();
+
+        let final_storage_usage = env::storage_usage();
+        near_sdk::log!(
+            "Created {} drops. Storage used: {}",
+            drop_ids.len(),
+            final_storage_usage - initial_storage_usage
+        );
 
         self.total_transactions += 1;
-        drop_id
+        drop_ids
     }
 
     /// Deletes a drop if the requestor is the creator or an admin.
@@ -246,8 +188,19 @@ impl Contract {
             "Only the drop creator can delete this drop"
         );
 
+        let drop_data = self.drop_by_id.remove(&drop_id);
+
+        // Refund whatever is left in the drop's budget to the creator
+        if let Some(budget) = drop_data
+            .as_ref()
+            .and_then(|drop| drop.get_budget())
+            .filter(|budget| !budget.is_zero())
+        {
+            self.internal_ft_deposit(&drop_creator, budget, false);
+        }
+
         // If the drop is an NFT drop and the series doesn't have any claims, delete the series
-        if let Some(DropData::Nft(nft_drop)) = self.drop_by_id.remove(&drop_id) {
+        if let Some(DropData::Nft(nft_drop)) = drop_data {
             self.internal_delete_series(nft_drop.nft_series_id);
         }
 
@@ -12,34 +12,277 @@ pub fn parse_drop_id(drop_id: &DropId) -> AccountId {
     AccountId::try_from(split[0].to_string()).expect("invalid account Id")
 }
 
+/// Ensures each milestone is reachable and that no two milestones share the same threshold.
+pub(crate) fn assert_valid_milestones(
+    milestones: &[ScavengerMilestone],
+    scavenger_hunt: Option<&[ScavengerHuntData]>,
+) {
+    let num_pieces = scavenger_hunt
+        .expect("Scavenger milestones require a scavenger hunt")
+        .len() as u16;
+    for (i, milestone) in milestones.iter().enumerate() {
+        require!(
+            milestone.pieces_found > 0 && milestone.pieces_found <= num_pieces,
+            "Scavenger milestone must be between 1 and the number of pieces"
+        );
+        require!(
+            !milestones[..i]
+                .iter()
+                .any(|other| other.pieces_found == milestone.pieces_found),
+            "Scavenger milestones must have unique thresholds"
+        );
+    }
+}
+
+/// Returns the scavenger piece with the lowest ID that hasn't been found yet.
+pub(crate) fn next_scavenger_piece<'a>(
+    scavenger_hunt: &'a [ScavengerHuntData],
+    found_scavenger_ids: &[PublicKey],
+) -> Option<&'a ScavengerHuntData> {
+    scavenger_hunt
+        .iter()
+        .filter(|piece| !found_scavenger_ids.contains(&piece.key))
+        .min_by_key(|piece| piece.id)
+}
+
 use std::convert::TryInto;
 
+impl Contract {
+    /// Internal method for creating a drop of any type. The drop ID is a concatenation of the
+    /// creator, delimiter, and the creator's drop nonce.
+    pub(crate) fn internal_create_drop(
+        &mut self,
+        drop_creator: &AccountId,
+        drop: DropCreationArgs,
+    ) -> DropId {
+        let config = drop.config.unwrap_or_default();
+        config.assert_valid();
+
+        let account_details = self
+            .account_details_by_id
+            .entry(drop_creator.clone())
+            .or_insert_with(|| AccountDetails::new(drop_creator));
+
+        // The drop ID will be a concatenation of the creator, delimiter, and the drop number
+        let drop_id = format!(
+            "{}{}{}",
+            drop_creator, DROP_DELIMITER, account_details.drop_nonce
+        );
+
+        // Add the drop ID to the creator's list of drop IDs
+        account_details.drops_created.insert(drop_id.clone());
+        account_details.drop_nonce += 1;
+        let creator_is_admin = account_details
+            .account_status
+            .as_ref()
+            .is_some_and(|status| status.is_admin());
+
+        let num_scavengers = drop
+            .scavenger_hunt
+            .as_ref()
+            .map(|scavenger_hunt| scavenger_hunt.len() as u16);
+
+        if let Some(milestones) = drop.scavenger_milestones.as_ref() {
+            assert_valid_milestones(milestones, drop.scavenger_hunt.as_deref());
+        }
+
+        // Sponsors need to set aside the tokens for every claim up front while admins mint them
+        let budget = if creator_is_admin {
+            None
+        } else {
+            Some(NearToken::from_yoctonear(0))
+        };
+
+        let (mut drop_data, drop_reward) = match drop.reward {
+            DropCreationReward::Token { token_amount } => (
+                DropData::Token(TokenDropData {
+                    id: drop_id.clone(),
+                    key: drop.key,
+                    name: drop.name,
+                    image: drop.image,
+                    scavenger_hunt: drop.scavenger_hunt,
+                    scavenger_milestones: drop.scavenger_milestones,
+                    num_claimed: 0,
+                    num_rewarded: 0,
+                    config,
+                    budget,
+                    token_amount,
+                }),
+                DropClaimReward::Token(token_amount),
+            ),
+            DropCreationReward::Nft { nft_metadata } => {
+                // Create the series
+                let series_id = self.series_by_id.len();
+                let tokens = IterableSet::new(StorageKeys::SeriesByIdInner {
+                    account_id_hash: hash_string(&format!("{}{}", drop_creator, series_id)),
+                });
+                let series = Series {
+                    metadata: nft_metadata.clone(),
+                    royalty: None,
+                    tokens,
+                };
+                require!(
+                    self.series_by_id.insert(series_id, series).is_none(),
+                    "Series ID already exists on the contract"
+                );
+
+                (
+                    DropData::Nft(NFTDropData {
+                        id: drop_id.clone(),
+                        key: drop.key,
+                        name: drop.name,
+                        image: drop.image,
+                        scavenger_hunt: drop.scavenger_hunt,
+                        scavenger_milestones: drop.scavenger_milestones,
+                        num_claimed: 0,
+                        num_rewarded: 0,
+                        config,
+                        budget,
+                        nft_metadata,
+                        nft_series_id: series_id,
+                    }),
+                    DropClaimReward::Nft,
+                )
+            }
+            DropCreationReward::Multichain {
+                multichain_metadata,
+                nft_metadata,
+            } => {
+                require!(
+                    parse_evm_address(&multichain_metadata.contract_id).is_some(),
+                    "Contract ID must be a 0x prefixed EVM address"
+                );
+                (
+                    DropData::Multichain(MultichainDropData {
+                        id: drop_id.clone(),
+                        key: drop.key,
+                        name: drop.name,
+                        image: drop.image,
+                        scavenger_hunt: drop.scavenger_hunt,
+                        scavenger_milestones: drop.scavenger_milestones,
+                        num_claimed: 0,
+                        num_rewarded: 0,
+                        config,
+                        budget,
+                        nft_metadata,
+                        mc_metadata: multichain_metadata,
+                    }),
+                    DropClaimReward::Multichain,
+                )
+            }
+        };
+        self.internal_rebalance_token_budget(drop_creator, &mut drop_data);
+
+        require!(
+            self.drop_by_id.insert(drop_id.clone(), drop_data).is_none(),
+            "Drop ID already exists"
+        );
+
+        let drop_creation_log: EventLog = EventLog {
+            standard: KEYPOM_STANDARD_NAME.to_string(),
+            version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
+            event: EventLogVariant::KeypomDropCreation(KeypomDropCreationLog {
+                creator_id: drop_creator.to_string(),
+                drop_reward,
+                num_scavengers,
+            }),
+        };
+        env::log_str(&drop_creation_log.to_string());
+
+        drop_id
+    }
+
+    /// Moves tokens between the creator's balance and a sponsor drop's budget so that the budget
+    /// covers exactly the tokens for the claims left on the drop's reward and on each of its
+    /// scavenger milestones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the drop pays out tokens without a max claims limit or if the creator doesn't
+    /// have enough tokens to cover the remaining claims.
+    pub(crate) fn internal_rebalance_token_budget(
+        &mut self,
+        drop_creator: &AccountId,
+        drop: &mut DropData,
+    ) {
+        let Some(budget) = drop.get_budget() else {
+            return;
+        };
+
+        let required_budget = if !drop.pays_out_tokens() {
+            NearToken::from_yoctonear(0)
+        } else {
+            let max_claims = drop
+                .get_config()
+                .max_claims
+                .expect("Drops that pay out tokens from a sponsor require max claims to be set");
+            NearToken::from_yoctonear(drop.get_required_budget(max_claims))
+        };
+
+        if required_budget > budget {
+            let amount_to_reserve = required_budget.saturating_sub(budget);
+            require!(
+                self.ft_balance_of(drop_creator.clone()) >= amount_to_reserve,
+                "The creator does not have enough tokens to fund this drop"
+            );
+            self.internal_ft_withdraw(drop_creator, amount_to_reserve);
+        } else if budget > required_budget {
+            self.internal_ft_deposit(drop_creator, budget.saturating_sub(required_budget), false);
+        }
+
+        drop.set_budget(required_budget);
+    }
+}
+
+/// Version tag at the start of replay-resistant claim messages.
+pub const CLAIM_MESSAGE_VERSION: &str = "kpom-claim-v1";
+
+/// Verifies that the claim message was signed by the expected key. ed25519 keys are checked with
+/// `ed25519_verify` while secp256k1 keys are checked by recovering the signer of the EIP-191
+/// personal message hash with `ecrecover`, so the message can be signed with `personal_sign`.
+///
+/// The message is `"{version},{drop_id},{caller_id},{key},{nonce},{expires_at}"` if claim params
+/// are passed in, otherwise the legacy `"{caller_id},{key}"` message is expected.
 pub(crate) fn verify_signature(
     signature: Base64VecU8,
     caller_id: AccountId,
     expected_key: PublicKey,
+    claim_params: Option<ClaimSignatureParams>,
 ) -> bool {
     // Extract the key bytes without the curve type prefix
     let key_bytes = expected_key.as_bytes();
     let key_bytes_without_prefix = &key_bytes[1..]; // Skip the first byte
 
-    // Convert the key bytes slice to a reference to a 32-byte array
-    let key_bytes_array: &[u8; 32] = key_bytes_without_prefix
-        .try_into()
-        .expect("Invalid key length");
-
     // Serialize the public key to base58
     let expected_key_base58 = bs58::encode(key_bytes_without_prefix).into_string();
 
     // The message that should have been signed
-    let expected_message = format!("{},{}", caller_id, expected_key_base58);
-
-    // Convert the signature into a 64-byte array
-    let sig_bytes =
-        vec_to_64_byte_array(signature.clone().into()).expect("Invalid signature length");
+    let expected_message = match claim_params {
+        Some(params) => format!(
+            "{},{},{},{},{},{}",
+            CLAIM_MESSAGE_VERSION,
+            params.drop_id,
+            caller_id,
+            expected_key_base58,
+            params.nonce,
+            params.expires_at
+        ),
+        None => format!("{},{}", caller_id, expected_key_base58),
+    };
 
-    // Verify the signature using the key bytes array
-    let is_valid = env::ed25519_verify(&sig_bytes, expected_message.as_bytes(), key_bytes_array);
+    // Verify the signature based on the curve of the expected key
+    let is_valid = match expected_key.curve_type() {
+        CurveType::ED25519 => verify_ed25519_signature(
+            signature.clone().into(),
+            expected_message.as_bytes(),
+            key_bytes_without_prefix,
+        ),
+        CurveType::SECP256K1 => verify_secp256k1_signature(
+            signature.clone().into(),
+            expected_message.as_bytes(),
+            key_bytes_without_prefix,
+        ),
+    };
 
     if !is_valid {
         env::log_str(
@@ -54,6 +297,36 @@ pub(crate) fn verify_signature(
     is_valid
 }
 
+fn verify_ed25519_signature(signature: Vec<u8>, message: &[u8], key_bytes: &[u8]) -> bool {
+    // Convert the key bytes slice to a reference to a 32-byte array
+    let key_bytes_array: &[u8; 32] = key_bytes.try_into().expect("Invalid key length");
+
+    // Convert the signature into a 64-byte array
+    let sig_bytes = vec_to_64_byte_array(signature).expect("Invalid signature length");
+
+    env::ed25519_verify(&sig_bytes, message, key_bytes_array)
+}
+
+/// The signature is expected as 65 bytes: `r`, `s` and the recovery ID `v`. Both raw (0 or 1)
+/// and EVM style (27 or 28) recovery IDs are accepted.
+pub(crate) fn verify_secp256k1_signature(
+    signature: Vec<u8>,
+    message: &[u8],
+    key_bytes: &[u8],
+) -> bool {
+    require!(key_bytes.len() == 64, "Invalid key length");
+    require!(signature.len() == 65, "Invalid signature length");
+
+    let recovery_id = match signature[64] {
+        v @ 0..=1 => v,
+        v @ 27..=28 => v - 27,
+        _ => return false,
+    };
+
+    env::ecrecover(&eip191_hash(message), &signature[..64], recovery_id, true)
+        .is_some_and(|recovered_key| recovered_key[..] == *key_bytes)
+}
+
 pub(crate) fn vec_to_64_byte_array(vec: Vec<u8>) -> Option<[u8; 64]> {
     // Check if the string is exactly 64 bytes
     if vec.len() != 64 {
@@ -69,3 +342,11 @@ pub(crate) fn vec_to_64_byte_array(vec: Vec<u8>) -> Option<[u8; 64]> {
 
     Some(array)
 }
+
+/// Hashes a message the way Ethereum wallets do for `personal_sign`:
+/// `keccak256("\x19Ethereum Signed Message:\n" || len(message) || message)`.
+pub(crate) fn eip191_hash(message: &[u8]) -> [u8; 32] {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    env::keccak256_array(&prefixed)
+}
@@ -2,6 +2,7 @@ pub mod claim_drop;
 pub mod create_drop;
 pub mod internal;
 pub mod models;
+pub mod update_drop;
 pub mod views;
 
 pub use internal::*;
@@ -1,3 +1,5 @@
+use near_sdk::serde::Deserializer;
+
 use crate::*;
 
 /// Represents the different types of claimed drops to be returned to the frontend.
@@ -62,6 +64,140 @@ impl DropData {
         }
     }
 
+    /// Returns the next scavenger piece the claimer needs to find if the hunt is ordered.
+    pub fn get_next_scavenger_piece(
+        &self,
+        found_scavenger_ids: &ScavengerKeys,
+    ) -> Option<ScavengerHuntData> {
+        if !self.get_config().ordered_scavenger_hunt {
+            return None;
+        }
+
+        let scavenger_hunt = self.get_scavenger_data()?;
+        next_scavenger_piece(
+            &scavenger_hunt,
+            found_scavenger_ids.as_deref().unwrap_or_default(),
+        )
+        .cloned()
+    }
+
+    /// Returns the milestone reached once the claimer has found the given number of pieces.
+    pub fn get_scavenger_milestone(&self, pieces_found: u16) -> Option<ScavengerMilestone> {
+        let milestones = match self {
+            DropData::Token(data) => data.scavenger_milestones.as_ref(),
+            DropData::Nft(data) => data.scavenger_milestones.as_ref(),
+            DropData::Multichain(data) => data.scavenger_milestones.as_ref(),
+        };
+        milestones?
+            .iter()
+            .find(|milestone| milestone.pieces_found == pieces_found)
+            .cloned()
+    }
+
+    pub fn get_scavenger_milestone_mut(
+        &mut self,
+        pieces_found: u16,
+    ) -> Option<&mut ScavengerMilestone> {
+        let milestones = match self {
+            DropData::Token(data) => data.scavenger_milestones.as_mut(),
+            DropData::Nft(data) => data.scavenger_milestones.as_mut(),
+            DropData::Multichain(data) => data.scavenger_milestones.as_mut(),
+        };
+        milestones?
+            .iter_mut()
+            .find(|milestone| milestone.pieces_found == pieces_found)
+    }
+
+    /// Returns whether the drop pays out any tokens, either as its own reward or through a
+    /// scavenger milestone.
+    pub fn pays_out_tokens(&self) -> bool {
+        let (drop_tokens, milestones) = match self {
+            DropData::Token(data) => (data.token_amount.0, data.scavenger_milestones.as_ref()),
+            DropData::Nft(data) => (0, data.scavenger_milestones.as_ref()),
+            DropData::Multichain(data) => (0, data.scavenger_milestones.as_ref()),
+        };
+        drop_tokens > 0
+            || milestones.is_some_and(|milestones| milestones.iter().any(|m| m.token_amount.0 > 0))
+    }
+
+    /// Returns the tokens needed to pay out the drop's own reward and each scavenger milestone
+    /// until they have all been handed out `max_claims` times.
+    pub fn get_required_budget(&self, max_claims: u64) -> u128 {
+        let (drop_tokens, milestones) = match self {
+            DropData::Token(data) => (data.token_amount.0, data.scavenger_milestones.as_ref()),
+            DropData::Nft(data) => (0, data.scavenger_milestones.as_ref()),
+            DropData::Multichain(data) => (0, data.scavenger_milestones.as_ref()),
+        };
+        let tokens_for = |amount: u128, num_paid: u64| {
+            amount
+                .checked_mul(max_claims.saturating_sub(num_paid) as u128)
+                .expect("NearToken overflow")
+        };
+
+        let milestone_tokens = milestones
+            .map(|milestones| {
+                milestones
+                    .iter()
+                    .map(|m| tokens_for(m.token_amount.0, m.num_paid))
+                    .fold(0u128, |total, tokens| {
+                        total.checked_add(tokens).expect("NearToken overflow")
+                    })
+            })
+            .unwrap_or(0);
+        tokens_for(drop_tokens, self.get_num_rewarded())
+            .checked_add(milestone_tokens)
+            .expect("NearToken overflow")
+    }
+
+    pub fn get_budget(&self) -> Option<NearToken> {
+        match self {
+            DropData::Token(data) => data.budget,
+            DropData::Nft(data) => data.budget,
+            DropData::Multichain(data) => data.budget,
+        }
+    }
+
+    pub fn set_budget(&mut self, budget: NearToken) {
+        match self {
+            DropData::Token(data) => data.budget = Some(budget),
+            DropData::Nft(data) => data.budget = Some(budget),
+            DropData::Multichain(data) => data.budget = Some(budget),
+        }
+    }
+
+    pub fn get_config(&self) -> &DropConfig {
+        match self {
+            DropData::Token(data) => &data.config,
+            DropData::Nft(data) => &data.config,
+            DropData::Multichain(data) => &data.config,
+        }
+    }
+
+    pub fn get_config_mut(&mut self) -> &mut DropConfig {
+        match self {
+            DropData::Token(data) => &mut data.config,
+            DropData::Nft(data) => &mut data.config,
+            DropData::Multichain(data) => &mut data.config,
+        }
+    }
+
+    pub fn get_num_claimed(&self) -> u64 {
+        match self {
+            DropData::Token(data) => data.num_claimed,
+            DropData::Nft(data) => data.num_claimed,
+            DropData::Multichain(data) => data.num_claimed,
+        }
+    }
+
+    /// Returns the number of times the drop's reward has been handed out.
+    pub fn get_num_rewarded(&self) -> u64 {
+        match self {
+            DropData::Token(data) => data.num_rewarded,
+            DropData::Nft(data) => data.num_rewarded,
+            DropData::Multichain(data) => data.num_rewarded,
+        }
+    }
+
     /// Returns the name of the claimed drop.
     pub fn get_name(&self) -> String {
         match self {
@@ -80,8 +216,168 @@ pub struct ScavengerHuntData {
     pub description: String,
 }
 
+/// Tokens handed out once a claimer has found a given number of scavenger pieces. The drop's own
+/// reward is still handed out once every piece has been found.
+///
+/// Each milestone can be paid out up to the drop's max claims, separately from the drop's own
+/// reward, and sponsor drops set aside `token_amount` for each of those payouts.
+#[derive(Clone, Debug)]
+#[near(serializers = [json, borsh])]
+pub struct ScavengerMilestone {
+    pub pieces_found: u16,
+    pub token_amount: U128,
+    // Number of times the milestone has been paid out
+    #[serde(default, skip_deserializing)]
+    pub num_paid: u64,
+}
+
 pub type ScavengerKeys = Option<Vec<PublicKey>>;
 
+/// Limits the drop creator can place on when and how many times a drop can be claimed.
+/// Timestamps are block timestamps in nanoseconds.
+#[derive(Clone, Default)]
+#[near(serializers = [json, borsh])]
+pub struct DropConfig {
+    // Maximum number of times the reward can be handed out
+    pub max_claims: Option<u64>,
+    // The drop can't be claimed before this timestamp
+    pub starts_at: Option<u64>,
+    // The drop can't be claimed after this timestamp
+    pub ends_at: Option<u64>,
+    #[serde(default)]
+    pub paused: bool,
+    // Scavenger pieces must be found in order of their IDs
+    #[serde(default)]
+    pub ordered_scavenger_hunt: bool,
+    // Accept the legacy claim signature that only covers the claimer and key and can be replayed
+    #[serde(default)]
+    pub legacy_claim_signatures: bool,
+}
+
+/// Extra data covered by a replay-resistant claim signature.
+pub struct ClaimSignatureParams {
+    pub drop_id: DropId,
+    pub nonce: u64,
+    pub expires_at: u64,
+}
+
+impl DropConfig {
+    /// Ensures the start and end timestamps make sense together.
+    pub fn assert_valid(&self) {
+        if let (Some(starts_at), Some(ends_at)) = (self.starts_at, self.ends_at) {
+            require!(starts_at < ends_at, "Drop must start before it ends");
+        }
+    }
+
+    /// Ensures the drop isn't paused and that the current block is within the claim window.
+    pub fn assert_claimable(&self) {
+        require!(!self.paused, "Drop is paused");
+        let now = env::block_timestamp();
+        if let Some(starts_at) = self.starts_at {
+            require!(now >= starts_at, "Drop has not started yet");
+        }
+        if let Some(ends_at) = self.ends_at {
+            require!(now <= ends_at, "Drop has ended");
+        }
+    }
+
+    /// Returns whether the reward can be handed out again given how many times it already has.
+    pub fn has_claims_left(&self, num_rewarded: u64) -> bool {
+        self.max_claims
+            .is_none_or(|max_claims| num_rewarded < max_claims)
+    }
+}
+
+/// Changes to a drop's claim limits. Fields that are left out keep their current value, and the
+/// max claims and claim window can be removed by passing `null`.
+#[derive(Clone, Default)]
+#[near(serializers = [json])]
+pub struct DropConfigUpdate {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub max_claims: Option<Option<u64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub starts_at: Option<Option<u64>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub ends_at: Option<Option<u64>>,
+    pub paused: Option<bool>,
+    pub ordered_scavenger_hunt: Option<bool>,
+    pub legacy_claim_signatures: Option<bool>,
+}
+
+impl DropConfigUpdate {
+    /// Whether the update doesn't change anything.
+    pub fn is_empty(&self) -> bool {
+        self.max_claims.is_none()
+            && self.starts_at.is_none()
+            && self.ends_at.is_none()
+            && self.paused.is_none()
+            && self.ordered_scavenger_hunt.is_none()
+            && self.legacy_claim_signatures.is_none()
+    }
+
+    /// Merges the fields that were passed into the config.
+    pub fn apply(self, config: &mut DropConfig) {
+        if let Some(max_claims) = self.max_claims {
+            config.max_claims = max_claims;
+        }
+        if let Some(starts_at) = self.starts_at {
+            config.starts_at = starts_at;
+        }
+        if let Some(ends_at) = self.ends_at {
+            config.ends_at = ends_at;
+        }
+        if let Some(paused) = self.paused {
+            config.paused = paused;
+        }
+        if let Some(ordered_scavenger_hunt) = self.ordered_scavenger_hunt {
+            config.ordered_scavenger_hunt = ordered_scavenger_hunt;
+        }
+        if let Some(legacy_claim_signatures) = self.legacy_claim_signatures {
+            config.legacy_claim_signatures = legacy_claim_signatures;
+        }
+    }
+}
+
+// Deserializes a field that is present, including an explicit `null`, as `Some` so that it can be
+// told apart from a field that was left out
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Arguments for creating a single drop, used when creating drops in a batch.
+#[derive(Clone)]
+#[near(serializers = [json])]
+pub struct DropCreationArgs {
+    pub image: String,
+    pub name: String,
+    pub key: PublicKey,
+    pub scavenger_hunt: Option<Vec<ScavengerHuntData>>,
+    pub scavenger_milestones: Option<Vec<ScavengerMilestone>>,
+    pub config: Option<DropConfig>,
+    pub reward: DropCreationReward,
+}
+
+/// The reward specific arguments for each type of drop that can be created.
+#[derive(Clone)]
+#[near(serializers = [json])]
+#[serde(tag = "type")]
+pub enum DropCreationReward {
+    Token {
+        token_amount: U128,
+    },
+    Nft {
+        nft_metadata: TokenMetadata,
+    },
+    Multichain {
+        multichain_metadata: MultichainMetadata,
+        nft_metadata: TokenMetadata,
+    },
+}
+
 #[derive(Clone)]
 #[near(serializers = [json, borsh])]
 pub struct TokenDropData {
@@ -90,11 +386,35 @@ pub struct TokenDropData {
     pub name: String,
     pub image: String,
     pub scavenger_hunt: Option<Vec<ScavengerHuntData>>,
+    pub scavenger_milestones: Option<Vec<ScavengerMilestone>>,
     pub num_claimed: u64,
+    pub num_rewarded: u64,
+    pub config: DropConfig,
+    /// Tokens set aside from a sponsor's balance to pay out the remaining claims. Drops created
+    /// by admins mint their rewards instead and have no budget, as do sponsor drops created before
+    /// budgets were reserved, which pay out of the sponsor's balance.
+    pub budget: Option<NearToken>,
 
     pub token_amount: U128,
 }
 
+impl TokenDropData {
+    /// Returns whether the drop can pay out another claim. Drops with a budget report what's left
+    /// in the budget while admin drops are always funded since their tokens are minted.
+    pub fn has_funds(&self, creator: &AccountDetails) -> bool {
+        match self.budget {
+            Some(budget) => budget.as_yoctonear() >= self.token_amount.0,
+            None => match creator.account_status.as_ref() {
+                Some(status) if status.is_admin() => true,
+                Some(status) if status.is_sponsor() => {
+                    creator.ft_balance.as_yoctonear() >= self.token_amount.0
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
 /// Represents the internal data for a token drop.
 #[derive(Clone)]
 #[near(serializers = [json, borsh])]
@@ -104,7 +424,13 @@ pub struct NFTDropData {
     pub name: String,
     pub image: String,
     pub scavenger_hunt: Option<Vec<ScavengerHuntData>>,
+    pub scavenger_milestones: Option<Vec<ScavengerMilestone>>,
     pub num_claimed: u64,
+    pub num_rewarded: u64,
+    pub config: DropConfig,
+    /// Tokens set aside from a sponsor's balance to pay out the remaining claims. Drops created
+    /// by admins mint their rewards instead and have no budget.
+    pub budget: Option<NearToken>,
 
     pub nft_metadata: TokenMetadata,
     pub nft_series_id: SeriesId,
@@ -119,7 +445,13 @@ pub struct MultichainDropData {
     pub name: String,
     pub image: String,
     pub scavenger_hunt: Option<Vec<ScavengerHuntData>>,
+    pub scavenger_milestones: Option<Vec<ScavengerMilestone>>,
     pub num_claimed: u64,
+    pub num_rewarded: u64,
+    pub config: DropConfig,
+    /// Tokens set aside from a sponsor's balance to pay out the remaining claims. Drops created
+    /// by admins mint their rewards instead and have no budget.
+    pub budget: Option<NearToken>,
 
     pub nft_metadata: TokenMetadata,
     pub mc_metadata: MultichainMetadata,
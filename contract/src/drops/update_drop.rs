@@ -0,0 +1,209 @@
+use crate::*;
+
+#[near]
+impl Contract {
+    /// Allows the drop creator to change the claim limits on an existing drop such as the maximum
+    /// number of claims, the claim window, or whether the drop is paused.
+    ///
+    /// # Arguments
+    ///
+    /// * `drop_id` - The ID of the drop to be updated.
+    /// * `config` - The claim limits to change. Fields that are left out are kept as they are.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the drop is not found, if the requestor is not the creator, if nothing is being
+    /// updated, if the claim window is invalid, if the maximum number of claims is lower than the
+    /// number of rewards already handed out, or if the creator can't fund the budget for the new
+    /// number of claims.
+    pub fn update_drop_config(&mut self, drop_id: DropId, config: DropConfigUpdate) {
+        self.assert_no_freeze();
+        let caller_id = self.assert_sponsor();
+        let drop_creator = parse_drop_id(&drop_id);
+
+        // Ensure that only the creator of the drop can update it
+        require!(
+            drop_creator == caller_id,
+            "Only the drop creator can update this drop"
+        );
+        require!(!config.is_empty(), "Nothing to update");
+
+        let mut drop_data = self
+            .drop_by_id
+            .get(&drop_id)
+            .expect("Drop not found")
+            .clone();
+        let num_rewarded = drop_data.get_num_rewarded();
+        let drop_config = drop_data.get_config_mut();
+        config.apply(drop_config);
+        drop_config.assert_valid();
+        if let Some(max_claims) = drop_config.max_claims {
+            require!(
+                max_claims >= num_rewarded,
+                "Max claims cannot be lower than the number of claims already made"
+            );
+        }
+
+        // Reserve or refund tokens so the budget matches the new number of claims
+        self.internal_rebalance_token_budget(&drop_creator, &mut drop_data);
+        self.drop_by_id.insert(drop_id, drop_data);
+
+        self.total_transactions += 1;
+    }
+
+    /// Allows the drop creator or an admin to edit an existing drop without changing its ID so
+    /// that printed QR codes remain valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `drop_id` - The ID of the drop to be updated.
+    /// * `name` - Optional new name for the drop.
+    /// * `image` - Optional new image for the drop.
+    /// * `token_amount` - Optional new amount of tokens for a token drop. Cannot be changed once the drop has been claimed.
+    /// * `nft_metadata` - Optional new metadata for an NFT or multichain drop. For NFT drops the backing series is updated as well.
+    /// * `scavenger_pieces` - Optional scavenger pieces to append. Cannot be added once someone has completed the hunt.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the drop is not found, if the requestor is not the creator or an admin, or if an
+    /// update isn't allowed for the drop type or its current claims.
+    pub fn update_drop(
+        &mut self,
+        drop_id: DropId,
+        name: Option<String>,
+        image: Option<String>,
+        token_amount: Option<U128>,
+        nft_metadata: Option<TokenMetadata>,
+        scavenger_pieces: Option<Vec<ScavengerHuntData>>,
+    ) {
+        self.assert_no_freeze();
+        let caller_id = self.assert_sponsor();
+        let drop_creator = parse_drop_id(&drop_id);
+        let caller_is_admin = self
+            .account_details_by_id
+            .get(&caller_id)
+            .and_then(|details| details.account_status.as_ref())
+            .is_some_and(|status| status.is_admin());
+
+        // Ensure that only the creator of the drop or an admin can update it
+        require!(
+            drop_creator == caller_id || caller_is_admin,
+            "Only the drop creator or an admin can update this drop"
+        );
+
+        let mut drop_data = self
+            .drop_by_id
+            .get(&drop_id)
+            .expect("Drop not found")
+            .clone();
+        let num_claimed = drop_data.get_num_claimed();
+        let num_rewarded = drop_data.get_num_rewarded();
+        let mut updated_fields = Vec::new();
+
+        if let Some(name) = name {
+            match &mut drop_data {
+                DropData::Token(data) => data.name = name,
+                DropData::Nft(data) => data.name = name,
+                DropData::Multichain(data) => data.name = name,
+            }
+            updated_fields.push("name".to_string());
+        }
+
+        if let Some(image) = image {
+            match &mut drop_data {
+                DropData::Token(data) => data.image = image,
+                DropData::Nft(data) => data.image = image,
+                DropData::Multichain(data) => data.image = image,
+            }
+            updated_fields.push("image".to_string());
+        }
+
+        if let Some(token_amount) = token_amount {
+            let DropData::Token(data) = &mut drop_data else {
+                env::panic_str("Token amount can only be updated for token drops");
+            };
+            require!(
+                num_claimed == 0,
+                "Token amount cannot be updated once the drop has been claimed"
+            );
+            data.token_amount = token_amount;
+            self.internal_rebalance_token_budget(&drop_creator, &mut drop_data);
+            updated_fields.push("token_amount".to_string());
+        }
+
+        if let Some(nft_metadata) = nft_metadata {
+            match &mut drop_data {
+                DropData::Token(_) => {
+                    env::panic_str("NFT metadata can only be updated for NFT or multichain drops")
+                }
+                DropData::Nft(data) => {
+                    let series = self
+                        .series_by_id
+                        .get_mut(&data.nft_series_id)
+                        .expect("No series found with given ID");
+                    // Make sure the new copies limit still covers the NFTs already minted
+                    if let Some(copies) = nft_metadata.copies {
+                        require!(
+                            copies >= u64::from(series.tokens.len()),
+                            "Copies cannot be lower than the number of NFTs already minted"
+                        );
+                    }
+                    series.metadata = nft_metadata.clone();
+                    data.nft_metadata = nft_metadata;
+                }
+                DropData::Multichain(data) => data.nft_metadata = nft_metadata,
+            }
+            updated_fields.push("nft_metadata".to_string());
+        }
+
+        if let Some(scavenger_pieces) = scavenger_pieces {
+            // Adding pieces would change what people who already finished the hunt needed to find
+            require!(
+                num_rewarded == 0,
+                "Scavenger pieces cannot be added once the hunt has been completed"
+            );
+            let scavenger_hunt = match &mut drop_data {
+                DropData::Token(data) => &mut data.scavenger_hunt,
+                DropData::Nft(data) => &mut data.scavenger_hunt,
+                DropData::Multichain(data) => &mut data.scavenger_hunt,
+            };
+            // Drops without a hunt record claims with no pieces so they can't be turned into one
+            require!(
+                scavenger_hunt.is_some() || num_claimed == 0,
+                "Scavenger pieces cannot be added to a claimed drop without a scavenger hunt"
+            );
+
+            let pieces = scavenger_hunt.get_or_insert_with(Vec::new);
+            for piece in scavenger_pieces {
+                require!(
+                    !pieces
+                        .iter()
+                        .any(|existing| existing.key == piece.key || existing.id == piece.id),
+                    "Scavenger piece already exists"
+                );
+                pieces.push(piece);
+            }
+            updated_fields.push("scavenger_hunt".to_string());
+        }
+
+        require!(!updated_fields.is_empty(), "Nothing to update");
+        let num_scavengers = drop_data
+            .get_scavenger_data()
+            .map(|pieces| pieces.len() as u16);
+        self.drop_by_id.insert(drop_id.clone(), drop_data);
+
+        let drop_update_log: EventLog = EventLog {
+            standard: KEYPOM_STANDARD_NAME.to_string(),
+            version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
+            event: EventLogVariant::KeypomDropUpdate(KeypomDropUpdateLog {
+                drop_id,
+                updater_id: caller_id.to_string(),
+                updated_fields,
+                num_scavengers,
+            }),
+        };
+        env::log_str(&drop_update_log.to_string());
+
+        self.total_transactions += 1;
+    }
+}
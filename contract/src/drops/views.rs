@@ -18,7 +18,11 @@ pub struct ExtTokenDropData {
     pub name: String,
     pub image: String,
     pub scavenger_hunt: Option<Vec<ScavengerHuntData>>,
+    pub scavenger_milestones: Option<Vec<ScavengerMilestone>>,
     pub num_claimed: u64,
+    pub num_rewarded: u64,
+    pub config: DropConfig,
+    pub budget: Option<NearToken>,
     pub creator_has_funds: bool,
 
     pub token_amount: U128,
@@ -39,6 +43,7 @@ pub enum ExtClaimedDrop {
 pub struct ExtClaimedNFTDropData {
     pub found_scavenger_ids: ScavengerKeys,
     pub needed_scavenger_ids: Option<Vec<ScavengerHuntData>>,
+    pub next_scavenger_piece: Option<ScavengerHuntData>,
     pub name: String,
     pub image: String,
     pub nft_metadata: TokenMetadata,
@@ -51,6 +56,7 @@ pub struct ExtClaimedNFTDropData {
 pub struct ExtClaimedMultichainDropData {
     pub found_scavenger_ids: ScavengerKeys,
     pub needed_scavenger_ids: Option<Vec<ScavengerHuntData>>,
+    pub next_scavenger_piece: Option<ScavengerHuntData>,
     pub name: String,
     pub image: String,
     pub drop_id: DropId,
@@ -64,6 +70,7 @@ pub struct ExtClaimedMultichainDropData {
 pub struct ExtClaimedTokenDropData {
     pub found_scavenger_ids: ScavengerKeys,
     pub needed_scavenger_ids: Option<Vec<ScavengerHuntData>>,
+    pub next_scavenger_piece: Option<ScavengerHuntData>,
     pub creator_has_funds: bool,
     pub name: String,
     pub image: String,
@@ -94,10 +101,7 @@ impl Contract {
                 match drop_data {
                     DropData::Token(token_data) => {
                         // Compute creator_has_funds
-                        let creator_has_funds = match creator.account_status {
-                            Some(AccountStatus::Admin) => true,
-                            _ => creator.ft_balance.as_yoctonear() >= token_data.token_amount.0,
-                        };
+                        let creator_has_funds = token_data.has_funds(creator);
                         // Construct ExtTokenDropData
                         let ext_token_data = ExtTokenDropData {
                             id: token_data.id.clone(),
@@ -105,7 +109,11 @@ impl Contract {
                             name: token_data.name.clone(),
                             image: token_data.image.clone(),
                             scavenger_hunt: token_data.scavenger_hunt.clone(),
+                            scavenger_milestones: token_data.scavenger_milestones.clone(),
                             num_claimed: token_data.num_claimed,
+                            num_rewarded: token_data.num_rewarded,
+                            config: token_data.config.clone(),
+                            budget: token_data.budget,
                             creator_has_funds,
                             token_amount: token_data.token_amount,
                         };
@@ -119,7 +127,11 @@ impl Contract {
                             name: nft_data.name.clone(),
                             image: nft_data.image.clone(),
                             scavenger_hunt: nft_data.scavenger_hunt.clone(),
+                            scavenger_milestones: nft_data.scavenger_milestones.clone(),
                             num_claimed: nft_data.num_claimed,
+                            num_rewarded: nft_data.num_rewarded,
+                            config: nft_data.config.clone(),
+                            budget: nft_data.budget,
                             nft_metadata: nft_data.nft_metadata.clone(),
                             nft_series_id: nft_data.nft_series_id,
                         };
@@ -133,7 +145,11 @@ impl Contract {
                             name: mc_data.name.clone(),
                             image: mc_data.image.clone(),
                             scavenger_hunt: mc_data.scavenger_hunt.clone(),
+                            scavenger_milestones: mc_data.scavenger_milestones.clone(),
                             num_claimed: mc_data.num_claimed,
+                            num_rewarded: mc_data.num_rewarded,
+                            config: mc_data.config.clone(),
+                            budget: mc_data.budget,
                             nft_metadata: mc_data.nft_metadata.clone(),
                             mc_metadata: mc_data.mc_metadata.clone(),
                         };
@@ -191,6 +207,8 @@ impl Contract {
                                         ExtClaimedMultichainDropData {
                                             found_scavenger_ids: found_scavenger_ids.clone(),
                                             needed_scavenger_ids: drop_data.get_scavenger_data(),
+                                            next_scavenger_piece: drop_data
+                                                .get_next_scavenger_piece(found_scavenger_ids),
                                             name: multichain_data.name.clone(),
                                             image: multichain_data.image.clone(),
                                             mc_metadata: multichain_data.mc_metadata.clone(),
@@ -206,6 +224,8 @@ impl Contract {
                                     result_drops.push(ExtClaimedDrop::nft(ExtClaimedNFTDropData {
                                         found_scavenger_ids: found_scavenger_ids.clone(),
                                         needed_scavenger_ids: drop_data.get_scavenger_data(),
+                                        next_scavenger_piece: drop_data
+                                            .get_next_scavenger_piece(found_scavenger_ids),
                                         name: nft_data.name.clone(),
                                         image: nft_data.image.clone(),
                                         key: nft_data.key.clone(),
@@ -221,18 +241,14 @@ impl Contract {
                             }
                             DropData::Token(_) => {
                                 if let DropData::Token(token_data) = &drop_data {
-                                    let creator_has_funds = match creator.account_status {
-                                        Some(AccountStatus::Admin) => true,
-                                        _ => {
-                                            creator.ft_balance.as_yoctonear()
-                                                >= token_data.token_amount.0
-                                        }
-                                    };
+                                    let creator_has_funds = token_data.has_funds(creator);
                                     result_drops.push(ExtClaimedDrop::token(
                                         ExtClaimedTokenDropData {
                                             found_scavenger_ids: found_scavenger_ids.clone(),
                                             image: token_data.image.clone(),
                                             needed_scavenger_ids: drop_data.get_scavenger_data(),
+                                            next_scavenger_piece: drop_data
+                                                .get_next_scavenger_piece(found_scavenger_ids),
                                             creator_has_funds,
                                             key: token_data.key.clone(),
                                             name: token_data.name.clone(),
@@ -291,6 +307,8 @@ impl Contract {
                         found_scavenger_ids: found_scavenger_ids.clone(),
                         image: multichain_data.image.clone(),
                         needed_scavenger_ids: drop_data.get_scavenger_data(),
+                        next_scavenger_piece: drop_data
+                            .get_next_scavenger_piece(&found_scavenger_ids),
                         name: multichain_data.name.clone(),
                         mc_metadata: multichain_data.mc_metadata.clone(),
                         nft_metadata: multichain_data.nft_metadata.clone(),
@@ -306,6 +324,8 @@ impl Contract {
                     ExtClaimedDrop::nft(ExtClaimedNFTDropData {
                         found_scavenger_ids: found_scavenger_ids.clone(),
                         needed_scavenger_ids: drop_data.get_scavenger_data(),
+                        next_scavenger_piece: drop_data
+                            .get_next_scavenger_piece(&found_scavenger_ids),
                         image: nft_data.image.clone(),
                         name: nft_data.name.clone(),
                         key: nft_data.key.clone(),
@@ -323,15 +343,14 @@ impl Contract {
             }
             DropData::Token(_) => {
                 if let DropData::Token(token_data) = &drop_data {
-                    let creator_has_funds = match creator.account_status {
-                        Some(AccountStatus::Admin) => true,
-                        _ => creator.ft_balance.as_yoctonear() >= token_data.token_amount.0,
-                    };
+                    let creator_has_funds = token_data.has_funds(creator);
 
                     ExtClaimedDrop::token(ExtClaimedTokenDropData {
                         found_scavenger_ids: found_scavenger_ids.clone(),
                         key: token_data.key.clone(),
                         needed_scavenger_ids: drop_data.get_scavenger_data(),
+                        next_scavenger_piece: drop_data
+                            .get_next_scavenger_piece(&found_scavenger_ids),
                         creator_has_funds,
                         image: token_data.image.clone(),
                         name: token_data.name.clone(),
@@ -430,6 +449,22 @@ impl Contract {
         drops_created
     }
 
+    /// Retrieves the nonce that must be included in the account's next claim signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The ID of the account claiming drops.
+    ///
+    /// # Returns
+    ///
+    /// The claim nonce as a `u64`. Accounts that haven't claimed anything yet start at 0.
+    pub fn get_claim_nonce(&self, account_id: AccountId) -> u64 {
+        self.account_details_by_id
+            .get(&account_id)
+            .map(|details| details.claim_nonce)
+            .unwrap_or(0)
+    }
+
     /// Retrieves the total number of drops in the contract.
     ///
     /// # Returns
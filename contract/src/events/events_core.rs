@@ -23,6 +23,11 @@ pub enum EventLogVariant {
     KeypomTokenTransfer(KeypomTokenTransferLog),
     KeypomDropCreation(KeypomDropCreationLog),
     KeypomDropClaim(KeypomDropClaimLog),
+    KeypomDropUpdate(KeypomDropUpdateLog),
+    KeypomTicketTransfer(KeypomTicketTransferLog),
+    KeypomAccountCreationFailed(KeypomAccountCreationFailedLog),
+    KeypomOffboard(KeypomOffboardLog),
+    KeypomPurchase(KeypomPurchaseLog),
 }
 
 /// Interface to capture data about an event
@@ -46,4 +46,60 @@ pub struct KeypomDropClaimLog {
     pub reward: Option<DropClaimReward>,
     pub pieces_found: Option<u16>,
     pub pieces_required: Option<u16>,
+    pub milestone_reached: Option<u16>,
+    pub milestone_reward: Option<DropClaimReward>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KeypomDropUpdateLog {
+    pub drop_id: String,
+    pub updater_id: String,
+    pub updated_fields: Vec<String>,
+    pub num_scavengers: Option<u16>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KeypomTicketTransferLog {
+    pub drop_id: Option<String>,
+    pub old_public_key: String,
+    pub new_public_key: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KeypomAccountCreationFailedLog {
+    pub account_id: String,
+    pub public_key: String,
+    pub drop_id: Option<String>,
+    pub tokens_burned: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KeypomOffboardLog {
+    pub account_id: String,
+    pub destination_id: String,
+    pub token_ids: Vec<String>,
+    pub tokens_burned: U128,
+    pub amount_exchanged: Option<U128>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KeypomPurchasedItem {
+    pub item_id: String,
+    pub name: String,
+    pub quantity: u64,
+    pub price: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct KeypomPurchaseLog {
+    pub buyer_id: String,
+    pub vendor_id: String,
+    pub items: Vec<KeypomPurchasedItem>,
+    pub total: U128,
 }
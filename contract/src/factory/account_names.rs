@@ -0,0 +1,122 @@
+use crate::*;
+
+/// Maximum number of numbered suffixes tried when the requested account name is taken.
+pub const MAX_ACCOUNT_NAME_ATTEMPTS: u32 = 10;
+
+/// Returns the prefix of a requested account name, accepting either the prefix by itself or the
+/// full sub-account of the contract. Returns `None` if the prefix is empty or contains a `.`,
+/// since accounts can only be created one level below the contract.
+pub(crate) fn account_name_prefix(new_account_id: &AccountId) -> Option<String> {
+    let delim = format!(".{}", env::current_account_id());
+    let prefix = new_account_id
+        .as_str()
+        .strip_suffix(&delim)
+        .unwrap_or(new_account_id.as_str());
+    (!prefix.is_empty() && !prefix.contains('.')).then(|| prefix.to_string())
+}
+
+/// Builds the sub-account of the contract for the given prefix.
+///
+/// # Panics
+///
+/// Panics if the resulting account ID is invalid (e.g. too long).
+pub(crate) fn account_id_from_prefix(prefix: &str) -> AccountId {
+    format!("{}.{}", prefix, env::current_account_id())
+        .parse()
+        .expect("Invalid account name")
+}
+
+#[near]
+impl Contract {
+    /// Checks whether an account name can be reserved or created.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_account_id` - The requested account name, either the prefix or the full sub-account.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the name is valid and hasn't been taken or reserved through the contract,
+    /// otherwise `false`. Accounts created outside of the contract are not checked.
+    pub fn check_account_name_available(&self, new_account_id: AccountId) -> bool {
+        account_name_prefix(&new_account_id)
+            .and_then(|prefix| {
+                format!("{}.{}", prefix, env::current_account_id())
+                    .parse::<AccountId>()
+                    .ok()
+            })
+            .is_some_and(|account_id| self.is_account_name_available(&account_id))
+    }
+
+    /// Reserves an account name for the signing ticket so that it can't be taken before the
+    /// account is created. Reserving a new name releases any name the ticket reserved before.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_account_id` - The requested account name, either the prefix or the full sub-account.
+    ///
+    /// # Returns
+    ///
+    /// The full account ID that was reserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ticket does not exist, if its account has already been created, or if the
+    /// name is invalid or unavailable.
+    pub fn reserve_account_name(&mut self, new_account_id: AccountId) -> AccountId {
+        self.assert_no_freeze();
+        let ticket_pk = env::signer_account_pk();
+
+        let prefix = account_name_prefix(&new_account_id).expect("Invalid account name");
+        let account_id = account_id_from_prefix(&prefix);
+        require!(
+            self.is_account_name_available(&account_id),
+            "Account name is not available"
+        );
+
+        let attendee_ticket = self
+            .attendee_ticket_by_pk
+            .get_mut(&ticket_pk)
+            .expect("No ticket information found for public key");
+        require!(
+            attendee_ticket.account_id.is_none(),
+            "Account already created"
+        );
+
+        if let Some(previous) = attendee_ticket
+            .reserved_account_id
+            .replace(account_id.clone())
+        {
+            self.reserved_account_names.remove(&previous);
+        }
+        self.reserved_account_names.insert(account_id.clone());
+
+        self.total_transactions += 1;
+        account_id
+    }
+
+    /// Whether an account ID has neither been created through the contract nor reserved. Accounts
+    /// created outside of the contract aren't known here, so if one of them takes the name the
+    /// account creation fails and `resolve_account_creation` rolls the ticket back so that it can
+    /// try again with another name.
+    pub(crate) fn is_account_name_available(&self, account_id: &AccountId) -> bool {
+        !self.account_details_by_id.contains_key(account_id)
+            && !self.reserved_account_names.contains(account_id)
+    }
+
+    /// Finds the first available account ID for the prefix, trying `prefix`, `prefix-1`, ...
+    /// up to `MAX_ACCOUNT_NAME_ATTEMPTS` suffixes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no available account ID is found within the allowed attempts.
+    pub(crate) fn internal_find_available_account_name(&self, prefix: &str) -> AccountId {
+        (0..=MAX_ACCOUNT_NAME_ATTEMPTS)
+            .map(|i| match i {
+                0 => account_id_from_prefix(prefix),
+                _ => account_id_from_prefix(&format!("{}-{}", prefix, i)),
+            })
+            .find(|account_id| self.is_account_name_available(account_id))
+            .unwrap_or_else(|| env::panic_str("No available account name found"))
+    }
+}
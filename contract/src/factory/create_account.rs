@@ -1,41 +1,162 @@
-use near_sdk::{Allowance, Promise, PublicKey};
+use near_sdk::{Allowance, Gas, Promise, PromiseResult, PublicKey};
+
+use crate::factory::account_names::account_name_prefix;
 
 use crate::*;
 
+const GAS_FOR_RESOLVE_ACCOUNT_CREATION: Gas = Gas::from_tgas(20); // 20 TGas = 2 * 10^13 gas units
+
 #[near]
 impl Contract {
-    /// Scans the ticket into the event
+    /// Scans the ticket into a day or session of the event. Tickets can only scan themselves in
+    /// if an admin has allowed self check-in, otherwise they must be scanned by check-in staff.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The day or session to check into. Defaults to the general session.
     ///
     /// # Panics
     ///
-    /// Panics if the ticket has already been scanned or if the ticket does not exist.
+    /// Panics if self check-in isn't allowed, if the session is unknown, if the ticket has already
+    /// been scanned into the session, if the ticket doesn't grant access to the session, or if the
+    /// ticket does not exist.
     #[payable]
-    pub fn scan_ticket(&mut self) {
+    pub fn scan_ticket(&mut self, session: Option<String>) {
         self.assert_no_freeze();
+        require!(
+            self.allow_self_check_in,
+            "Tickets must be scanned by check-in staff"
+        );
         let ticket_pk = env::signer_account_pk();
 
+        self.internal_check_in(&ticket_pk, session, None, None);
+
+        self.total_transactions += 1;
+    }
+
+    /// Allows a check-in staff member to scan an attendee's ticket into a day or session. Records
+    /// who scanned the ticket, where and when, and counts the check-in towards the gate.
+    ///
+    /// # Arguments
+    ///
+    /// * `ticket_pk` - The public key of the ticket being scanned.
+    /// * `location` - The gate or location the ticket was scanned at.
+    /// * `session` - The day or session to check into. Defaults to the general session.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not a scanner, if the session is unknown, if the ticket does not
+    /// exist, if the ticket doesn't grant access to the session, or if the ticket has already been
+    /// scanned into the session.
+    pub fn scan_ticket_by_staff(
+        &mut self,
+        ticket_pk: PublicKey,
+        location: String,
+        session: Option<String>,
+    ) {
+        self.assert_no_freeze();
+        let scanner_id = self.assert_scanner();
+
+        self.internal_check_in(
+            &ticket_pk,
+            session,
+            Some(scanner_id),
+            Some(location.clone()),
+        );
+        *self.check_ins_by_location.entry(location).or_insert(0) += 1;
+
+        self.total_transactions += 1;
+    }
+
+    /// Allows an admin to let attendees scan their own tickets with `scan_ticket`, for example at
+    /// events without check-in staff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin.
+    pub fn set_allow_self_check_in(&mut self, allow: bool) {
+        self.assert_admin();
+        self.allow_self_check_in = allow;
+    }
+
+    /// Records a ticket's attendance for a day or session after making sure the session exists and
+    /// the ticket type grants access to it.
+    fn internal_check_in(
+        &mut self,
+        ticket_pk: &PublicKey,
+        session: Option<String>,
+        scanned_by: Option<AccountId>,
+        location: Option<String>,
+    ) {
+        let session = session.unwrap_or_else(|| DEFAULT_SESSION.to_string());
+        require!(self.is_known_session(&session), "Unknown session");
+
+        let ticket_data_by_id = &self.ticket_data_by_id;
         let attendee_ticket = self
             .attendee_ticket_by_pk
-            .get_mut(&ticket_pk)
+            .get_mut(ticket_pk)
             .expect("No ticket information found for public key");
         require!(
-            !attendee_ticket.has_scanned,
+            !attendee_ticket.has_checked_in(&session),
             "Ticket has already been scanned"
         );
-        attendee_ticket.has_scanned = true;
 
-        self.total_transactions += 1;
+        // Tickets without a ticket type (i.e. created by an admin) have access to everything
+        if let Some(ticket_type) = attendee_ticket
+            .drop_id
+            .as_ref()
+            .and_then(|drop_id| ticket_data_by_id.get(drop_id))
+        {
+            require!(
+                ticket_type.grants_access_to(&session),
+                "Ticket does not grant access to this session"
+            );
+        }
+
+        // Only the first check-in counts towards the ticket type's scanned tickets
+        if !attendee_ticket.has_scanned() {
+            if let Some(drop_id) = attendee_ticket.drop_id.clone() {
+                self.ticket_stats_by_id.entry(drop_id).or_default().scanned += 1;
+            }
+        }
+
+        attendee_ticket.check_ins.push(TicketCheckIn {
+            session: session.clone(),
+            scanned_at: env::block_timestamp(),
+            scanned_by,
+            location,
+        });
+
+        *self.attendance_by_session.entry(session).or_insert(0) += 1;
+    }
+
+    /// Sessions that tickets can be checked into are the default session and any session that a
+    /// ticket type grants access to. This keeps attendance from being recorded for arbitrary names.
+    fn is_known_session(&self, session: &str) -> bool {
+        session == DEFAULT_SESSION
+            || self.ticket_data_by_id.values().any(|ticket_type| {
+                ticket_type
+                    .sessions
+                    .as_ref()
+                    .is_some_and(|sessions| sessions.iter().any(|s| s == session))
+            })
     }
 
     /// Creates a new account with the given parameters.
     ///
     /// # Arguments
     ///
-    /// * `new_account_id` - The ID of the new account to be created.
+    /// * `new_account_id` - The ID of the new account to be created. Ignored if the ticket has
+    /// reserved an account name.
     ///
     /// # Returns
     ///
     /// Returns a promise to create the new account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ticket hasn't been scanned, if the account has already been created, or if
+    /// the name is invalid and no available name could be found.
     #[payable]
     pub fn create_account(&mut self, new_account_id: AccountId) -> Promise {
         self.assert_no_freeze();
@@ -43,10 +164,10 @@ impl Contract {
 
         let attendee_ticket = self
             .attendee_ticket_by_pk
-            .get_mut(&ticket_pk)
+            .get(&ticket_pk)
             .expect("No ticket information found for public key");
         require!(
-            attendee_ticket.has_scanned,
+            attendee_ticket.has_scanned(),
             "Ticket needs to be scanned first"
         );
         require!(
@@ -54,30 +175,23 @@ impl Contract {
             "Account already created"
         );
 
-        // Get the next available account ID in case the one passed in is taken
-        let delim = format!(".{}", env::current_account_id()).to_string();
-        let binding = new_account_id.to_string();
-        let split: Vec<&str> = binding.split(&delim).collect();
-        let prefix = split[0].to_string();
-
-        let mut account_id = new_account_id.clone();
-        let mut i = 0;
-
-        loop {
-            let is_new_account = self.account_details_by_id.get(&account_id).is_none();
-
-            if is_new_account {
-                break;
+        // Use the name reserved by the ticket, otherwise get the next available account ID in case
+        // the one passed in is taken
+        let account_id = match attendee_ticket.reserved_account_id.clone() {
+            Some(reserved_account_id) => {
+                self.reserved_account_names.remove(&reserved_account_id);
+                reserved_account_id
             }
-
-            i += 1;
-            account_id = format!("{}-{}.{}", prefix, i, env::current_account_id())
-                .parse()
-                .unwrap();
-        }
+            None => {
+                let prefix = account_name_prefix(&new_account_id).expect("Invalid account name");
+                self.internal_find_available_account_name(&prefix)
+            }
+        };
 
         // Update the attendee ticket with the new account ID and make sure that it doesnt get
         // scanned in again
+        let attendee_ticket = self.attendee_ticket_by_pk.get_mut(&ticket_pk).unwrap();
+        attendee_ticket.reserved_account_id = None;
         attendee_ticket.account_id = Some(account_id.clone());
 
         let ticket_drop_id = attendee_ticket
@@ -85,13 +199,18 @@ impl Contract {
             .clone()
             .expect("No drop ID found. Admin accounts should be created via internal functions");
         let ticket_data = self.ticket_data_by_id.get(&ticket_drop_id).unwrap();
+        self.ticket_stats_by_id
+            .entry(ticket_drop_id)
+            .or_default()
+            .accounts_created += 1;
 
         self.total_transactions += 1;
         self.internal_create_account(account_id, ticket_pk, ticket_data.clone(), false)
     }
 
     /// Creates a new account with the given parameters.
-    /// Initializes the account with the starting balances and account type.
+    /// Initializes the account with the starting balances and account type. If a previous
+    /// account creation for the key failed, whatever that account received is handed over.
     ///
     /// # Arguments
     ///
@@ -110,20 +229,36 @@ impl Contract {
     ) -> Promise {
         self.assert_no_freeze();
         self.assert_admin();
-
-        let attendee_info = AttendeeTicketInformation {
-            drop_id: None,
-            has_scanned: true,
-            account_id: Some(new_account_id.clone()),
-            metadata: None,
-        };
         require!(
-            self.attendee_ticket_by_pk
-                .insert(new_public_key.clone(), attendee_info)
-                .is_none(),
-            "Key already exists"
+            self.is_account_name_available(&new_account_id),
+            "Account name is not available"
         );
 
+        let attendee_info = match self.attendee_ticket_by_pk.remove(&new_public_key) {
+            // A key left behind by a failed account creation still holds what that account received
+            Some(ticket) => {
+                require!(
+                    ticket.drop_id.is_none() && ticket.account_id.is_none(),
+                    "Key already exists"
+                );
+                AttendeeTicketInformation {
+                    account_id: Some(new_account_id.clone()),
+                    ..ticket
+                }
+            }
+            None => AttendeeTicketInformation {
+                drop_id: None,
+                check_ins: Vec::new(),
+                reserved_account_id: None,
+                account_id: Some(new_account_id.clone()),
+                metadata: None,
+                held_tokens: NearToken::from_yoctonear(0),
+                held_nft_ids: Vec::new(),
+            },
+        };
+        self.attendee_ticket_by_pk
+            .insert(new_public_key.clone(), attendee_info);
+
         self.total_transactions += 1;
         self.internal_create_account(new_account_id, new_public_key, ticket_data, true)
     }
@@ -139,7 +274,8 @@ impl Contract {
     ///
     /// # Returns
     ///
-    /// Returns a promise to create the new account.
+    /// Returns a promise to create the new account that resolves to whether the account was
+    /// created.
     fn internal_create_account(
         &mut self,
         new_account_id: AccountId,
@@ -171,6 +307,14 @@ impl Contract {
                 account_details.account_status = Some(AccountStatus::TicketAdder);
                 access_key_method_names = TICKET_ADDER_KEY_METHOD_NAMES;
             }
+            AccountStatus::Scanner => {
+                account_details.account_status = Some(AccountStatus::Scanner);
+                access_key_method_names = SCANNER_KEY_METHOD_NAMES;
+            }
+            AccountStatus::Vendor => {
+                account_details.account_status = Some(AccountStatus::Vendor);
+                access_key_method_names = VENDOR_KEY_METHOD_NAMES;
+            }
             _ => {
                 // Do nothing for other cases, including AccountStatus::Basic
             }
@@ -188,6 +332,25 @@ impl Contract {
         // Deposit the starting balance into the account and then create it
         self.internal_deposit_ft_mint(&new_account_id, tokens_to_start, None, false);
 
+        // Hand over anything held for the ticket from a previous account creation that failed
+        if let Some(ticket) = self.attendee_ticket_by_pk.get_mut(&new_public_key) {
+            let held_tokens =
+                std::mem::replace(&mut ticket.held_tokens, NearToken::from_yoctonear(0));
+            let held_nft_ids = std::mem::take(&mut ticket.held_nft_ids);
+            if !held_tokens.is_zero() {
+                self.internal_ft_deposit(&new_account_id, held_tokens, false);
+            }
+            for token_id in held_nft_ids.iter() {
+                self.internal_transfer(
+                    &env::current_account_id(),
+                    &new_account_id,
+                    token_id,
+                    None,
+                    None,
+                );
+            }
+        }
+
         let final_storage_usage = env::storage_usage();
         near_sdk::log!(
             "Storage used: {}",
@@ -206,10 +369,163 @@ impl Contract {
             );
         }
 
-        // Add the same full access key to the account so that they can offboard later
-        Promise::new(new_account_id.clone())
+        // Add the same full access key to the account so that they can offboard later and deploy
+        // the wallet picked by the ticket type
+        let create_account = Promise::new(new_account_id.clone())
             .create_account()
             .transfer(near_to_start)
-            .add_full_access_key(new_public_key)
+            .add_full_access_key(new_public_key.clone());
+        self.internal_deploy_wallet(create_account, ticket_data.wallet_version.as_ref())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_ACCOUNT_CREATION)
+                    .with_unused_gas_weight(0)
+                    .resolve_account_creation(
+                        new_account_id,
+                        new_public_key,
+                        tokens_to_start,
+                        add_key,
+                    ),
+            )
+    }
+
+    /// Resolves the sub-account creation. If the account couldn't be created (i.e. it already
+    /// exists or the promise ran out of gas), the account details are removed, its starting
+    /// tokens are burned and the ticket is freed up so that the attendee can try again. Any tokens
+    /// and NFTs the account received on top of that are held on the ticket for its next account.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The ID of the account that was being created.
+    /// * `public_key` - The ticket's public key.
+    /// * `starting_token_balance` - The tokens minted to the account when it was initialized.
+    /// * `key_added` - Whether an access key was added to the contract for the public key.
+    ///
+    /// # Returns
+    ///
+    /// Returns whether the account was created.
+    #[private]
+    pub fn resolve_account_creation(
+        &mut self,
+        account_id: AccountId,
+        public_key: PublicKey,
+        starting_token_balance: NearToken,
+        key_added: bool,
+    ) -> bool {
+        // The batch may end with the wallet's init call, whose return value isn't ours to parse
+        if matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            return true;
+        }
+
+        // The ticket may have been revoked while the account was being created, in which case
+        // there's nothing to hold the account's tokens and they're all burned
+        let has_ticket = self
+            .attendee_ticket_by_pk
+            .get(&public_key)
+            .is_some_and(|ticket| ticket.account_id.as_ref() == Some(&account_id));
+
+        // The account may have been cleared while it was being created
+        let balance = self.ft_balance_of(account_id.clone());
+        let tokens_burned = if has_ticket {
+            balance.min(starting_token_balance)
+        } else {
+            balance
+        };
+        let tokens_held = balance.saturating_sub(tokens_burned);
+        if !tokens_burned.is_zero() {
+            self.internal_ft_burn(
+                &account_id,
+                tokens_burned,
+                Some("Account creation failed".to_string()),
+            );
+        }
+        if !tokens_held.is_zero() {
+            self.internal_ft_withdraw(&account_id, tokens_held);
+        }
+
+        // The contract holds on to the NFTs so they don't end up with whoever owns the account
+        let held_nft_ids: Vec<TokenId> = self
+            .nft_tokens_per_owner
+            .get(&account_id)
+            .map(|tokens| tokens.iter().cloned().collect())
+            .unwrap_or_default();
+        for token_id in held_nft_ids.iter() {
+            self.internal_transfer(
+                &account_id,
+                &env::current_account_id(),
+                token_id,
+                None,
+                Some("Account creation failed".to_string()),
+            );
+        }
+
+        if let Some(mut account_details) = self.account_details_by_id.remove(&account_id) {
+            account_details.drops_claimed.clear();
+            account_details.drops_created.clear();
+            account_details.multichain_mints.clear();
+            account_details.metadata_grants_received.clear();
+            account_details.metadata_grants_given.clear();
+            account_details.ft_allowances.clear();
+        }
+        self.token_leaderboard.retain(|id| id != &account_id);
+        self.poap_leaderboard.retain(|id| id != &account_id);
+
+        self.total_tokens_transferred = self
+            .total_tokens_transferred
+            .saturating_sub(starting_token_balance);
+
+        // Free up the ticket so that a new account can be created for it. Tickets created by an
+        // admin only exist for the account so their access key is removed, and so is the ticket
+        // unless it holds something for the admin's next attempt.
+        let mut drop_id = None;
+        if key_added {
+            Promise::new(env::current_account_id()).delete_key(public_key.clone());
+        }
+        if let Some(attendee_ticket) = self
+            .attendee_ticket_by_pk
+            .get_mut(&public_key)
+            .filter(|ticket| ticket.account_id.as_ref() == Some(&account_id))
+        {
+            attendee_ticket.account_id = None;
+            attendee_ticket.held_tokens = attendee_ticket.held_tokens.saturating_add(tokens_held);
+            attendee_ticket.held_nft_ids.extend(held_nft_ids);
+            let holds_nothing =
+                attendee_ticket.held_tokens.is_zero() && attendee_ticket.held_nft_ids.is_empty();
+            drop_id = attendee_ticket.drop_id.clone();
+            if let Some(stats) = drop_id
+                .as_ref()
+                .and_then(|drop_id| self.ticket_stats_by_id.get_mut(drop_id))
+            {
+                stats.accounts_created = stats.accounts_created.saturating_sub(1);
+            }
+
+            if key_added && holds_nothing {
+                self.attendee_ticket_by_pk.remove(&public_key);
+            }
+        }
+
+        near_sdk::log!(
+            "Failed to create account: {}. Rolling back {} tokens",
+            account_id,
+            tokens_burned.as_yoctonear()
+        );
+
+        env::log_str(
+            &EventLog {
+                standard: KEYPOM_STANDARD_NAME.to_string(),
+                version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
+                event: EventLogVariant::KeypomAccountCreationFailed(
+                    KeypomAccountCreationFailedLog {
+                        account_id: account_id.to_string(),
+                        public_key: String::from(&public_key),
+                        drop_id,
+                        tokens_burned: U128(tokens_burned.as_yoctonear()),
+                    },
+                ),
+            }
+            .to_string(),
+        );
+
+        false
     }
 }
@@ -1 +1,3 @@
+pub mod account_names;
 pub mod create_account;
+pub mod wallets;
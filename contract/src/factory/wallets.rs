@@ -0,0 +1,156 @@
+use near_sdk::{Gas, Promise};
+
+use crate::*;
+
+/// Gas attached to the init call of a freshly deployed wallet contract.
+pub const GAS_FOR_WALLET_INIT: Gas = Gas::from_tgas(30); // 30 TGas = 3 * 10^13 gas units
+
+#[near]
+impl Contract {
+    /// Allows an admin to upload a wallet contract that can be deployed to created accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version to store the wallet under.
+    /// * `code` - The WASM of the wallet contract.
+    /// * `init_method` - The method called on the account right after the wallet is deployed.
+    /// * `init_args` - The JSON arguments passed to the init method. Defaults to `{}`.
+    /// * `set_as_default` - Whether accounts whose ticket type doesn't pick a wallet get this one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin, if the code is empty or if the version already exists.
+    pub fn upload_wallet_code(
+        &mut self,
+        version: String,
+        code: Base64VecU8,
+        init_method: String,
+        init_args: Option<String>,
+        set_as_default: Option<bool>,
+    ) {
+        self.assert_admin();
+        let code: Vec<u8> = code.into();
+        require!(!code.is_empty(), "Wallet code cannot be empty");
+        require!(
+            !self.wallet_contracts.contains_key(&version),
+            "Wallet version already exists"
+        );
+
+        let wallet = WalletContract {
+            code_hash: env::sha256_array(&code).into(),
+            code_size: code.len() as u64,
+            init_method,
+            init_args: init_args.unwrap_or_else(|| "{}".to_string()),
+            uploaded_at: env::block_timestamp(),
+        };
+        near_sdk::log!(
+            "Uploaded wallet version {} ({} bytes)",
+            version,
+            wallet.code_size
+        );
+
+        self.wallet_code_by_version.insert(version.clone(), code);
+        self.wallet_contracts.insert(version.clone(), wallet);
+        if set_as_default.unwrap_or(false) {
+            self.default_wallet_version = Some(version);
+        }
+    }
+
+    /// Allows an admin to remove a wallet version that is no longer used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin, if the version doesn't exist or if it is the default
+    /// wallet or used by a ticket type.
+    pub fn remove_wallet_code(&mut self, version: String) {
+        self.assert_admin();
+        require!(
+            self.default_wallet_version.as_ref() != Some(&version),
+            "Cannot remove the default wallet version"
+        );
+        require!(
+            !self
+                .ticket_data_by_id
+                .values()
+                .any(|ticket_data| ticket_data.wallet_version.as_ref() == Some(&version)),
+            "Wallet version is used by a ticket type"
+        );
+        require!(
+            self.wallet_contracts.remove(&version).is_some(),
+            "Wallet version not found"
+        );
+        self.wallet_code_by_version.remove(&version);
+    }
+
+    /// Allows an admin to set the wallet deployed to accounts whose ticket type doesn't pick one.
+    /// Passing `None` creates those accounts without a wallet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin or if the version doesn't exist.
+    pub fn set_default_wallet_version(&mut self, version: Option<String>) {
+        self.assert_admin();
+        if let Some(version) = version.as_ref() {
+            self.assert_wallet_exists(version);
+        }
+        self.default_wallet_version = version;
+    }
+
+    /// Query for every wallet version that has been uploaded.
+    ///
+    /// # Returns
+    ///
+    /// A vector of tuples containing the version and its wallet information (without the code).
+    pub fn get_wallet_versions(&self) -> Vec<(String, WalletContract)> {
+        self.wallet_contracts
+            .iter()
+            .map(|(version, wallet)| (version.clone(), wallet.clone()))
+            .collect()
+    }
+
+    /// Query for the wallet deployed to accounts whose ticket type doesn't pick one.
+    pub fn get_default_wallet_version(&self) -> Option<String> {
+        self.default_wallet_version.clone()
+    }
+}
+
+impl Contract {
+    pub(crate) fn assert_wallet_exists(&self, version: &String) {
+        require!(
+            self.wallet_contracts.contains_key(version),
+            "Wallet version not found"
+        );
+    }
+
+    /// Adds the deploy and init actions of the ticket type's wallet (or the default wallet) to
+    /// the account creation promise. Accounts are created without a contract if neither is set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the wallet version doesn't exist.
+    pub(crate) fn internal_deploy_wallet(
+        &self,
+        promise: Promise,
+        wallet_version: Option<&String>,
+    ) -> Promise {
+        let Some(version) = wallet_version.or(self.default_wallet_version.as_ref()) else {
+            return promise;
+        };
+
+        let wallet = self
+            .wallet_contracts
+            .get(version)
+            .expect("Wallet version not found");
+        let code = self
+            .wallet_code_by_version
+            .get(version)
+            .expect("Wallet code not found");
+
+        promise.deploy_contract(code.clone()).function_call(
+            wallet.init_method.clone(),
+            wallet.init_args.clone().into_bytes(),
+            NearToken::from_yoctonear(0),
+            GAS_FOR_WALLET_INIT,
+        )
+    }
+}
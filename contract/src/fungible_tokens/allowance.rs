@@ -0,0 +1,199 @@
+use crate::*;
+
+/// Tokens a spender is allowed to transfer out of an owner's account, for example a booth
+/// worker paying out of a sponsor's budget.
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub struct FtAllowance {
+    // Tokens left for the spender to transfer
+    pub amount: NearToken,
+    // Block timestamp after which the allowance can no longer be used
+    pub expires_at: Option<u64>,
+}
+
+impl FtAllowance {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| env::block_timestamp() > expires_at)
+    }
+}
+
+#[near]
+impl Contract {
+    /// Allows an account to let a spender transfer up to an amount of its tokens. Approving a
+    /// spender that already has an allowance replaces it.
+    ///
+    /// If the call is signed with a ticket key, the owner is the ticket's account and no deposit
+    /// is needed. Otherwise the owner is the predecessor and exactly 1 yoctoNEAR must be attached.
+    ///
+    /// # Arguments
+    ///
+    /// * `spender_id` - The account allowed to transfer the tokens.
+    /// * `amount` - The maximum amount of tokens the spender can transfer.
+    /// * `expires_at` - An optional block timestamp after which the allowance can't be used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deposit is missing, if the owner has no account, if the spender is the owner
+    /// or if the allowance has already expired.
+    #[payable]
+    pub fn ft_approve(&mut self, spender_id: AccountId, amount: U128, expires_at: Option<U64>) {
+        self.assert_no_freeze();
+        let owner_id = self.ft_caller_id();
+        require!(owner_id != spender_id, "Cannot approve yourself");
+        require!(amount.0 > 0, "The amount should be a positive number");
+        let expires_at = expires_at.map(|expires_at| expires_at.0);
+        require!(
+            expires_at.is_none_or(|expires_at| expires_at > env::block_timestamp()),
+            "Allowance has already expired"
+        );
+
+        self.account_details_by_id
+            .get_mut(&owner_id)
+            .expect("Owner not found")
+            .ft_allowances
+            .insert(
+                spender_id.clone(),
+                FtAllowance {
+                    amount: NearToken::from_yoctonear(amount.0),
+                    expires_at,
+                },
+            );
+        near_sdk::log!(
+            "{} approved {} to transfer {} tokens",
+            owner_id,
+            spender_id,
+            amount.0
+        );
+
+        self.total_transactions += 1;
+    }
+
+    /// Allows an account to revoke a spender's allowance.
+    ///
+    /// # Arguments
+    ///
+    /// * `spender_id` - The account whose allowance should be revoked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deposit is missing or if the spender has no allowance.
+    #[payable]
+    pub fn ft_revoke(&mut self, spender_id: AccountId) {
+        let owner_id = self.ft_caller_id();
+        require!(
+            self.account_details_by_id
+                .get_mut(&owner_id)
+                .expect("Owner not found")
+                .ft_allowances
+                .remove(&spender_id)
+                .is_some(),
+            "No allowance found for spender"
+        );
+
+        self.total_transactions += 1;
+    }
+
+    /// Transfers tokens out of an owner's account using the allowance they gave the caller.
+    ///
+    /// If the call is signed with a ticket key, the spender is the ticket's account, no deposit is
+    /// needed and the receiver must be a sub-account of this contract. Otherwise the spender is
+    /// the predecessor and exactly 1 yoctoNEAR must be attached.
+    ///
+    /// # Arguments
+    ///
+    /// * `owner_id` - The account the tokens are transferred out of.
+    /// * `receiver_id` - The account receiving the tokens.
+    /// * `amount` - The amount of tokens to transfer.
+    /// * `memo` - An optional memo included in the transfer event.
+    ///
+    /// # Returns
+    ///
+    /// Returns the allowance left after the transfer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller has no allowance, if it has expired or is too low, if the receiver is
+    /// invalid or if the owner doesn't have enough tokens.
+    #[payable]
+    pub fn ft_transfer_from(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    ) -> U128 {
+        self.assert_no_freeze();
+        if env::predecessor_account_id() == env::current_account_id() {
+            require!(
+                receiver_id
+                    .to_string()
+                    .ends_with(env::current_account_id().as_str()),
+                "Invalid receiver ID"
+            );
+        }
+        let spender_id = self.ft_caller_id();
+        let amount_to_transfer = NearToken::from_yoctonear(amount.0);
+
+        // Use up the allowance, removing it once it's been spent
+        let owner_details = self
+            .account_details_by_id
+            .get_mut(&owner_id)
+            .expect("Owner not found");
+        let allowance = owner_details
+            .ft_allowances
+            .get_mut(&spender_id)
+            .expect("No allowance found for spender");
+        require!(!allowance.is_expired(), "Allowance has expired");
+        allowance.amount = allowance
+            .amount
+            .checked_sub(amount_to_transfer)
+            .expect("Amount exceeds allowance");
+        let amount_left = allowance.amount;
+        if amount_left.is_zero() {
+            owner_details.ft_allowances.remove(&spender_id);
+        }
+
+        self.internal_ft_transfer(&owner_id, &receiver_id, amount_to_transfer, false, memo);
+        self.add_transaction(TransactionType::Transfer {
+            sender_id: owner_id,
+            receiver_id,
+            amount: amount_to_transfer,
+            timestamp: env::block_timestamp(),
+        });
+        self.total_transactions += 1;
+
+        U128(amount_left.as_yoctonear())
+    }
+
+    /// Query for the tokens a spender can still transfer out of an owner's account.
+    ///
+    /// # Returns
+    ///
+    /// Returns the remaining allowance, or zero if there is none or it has expired.
+    pub fn ft_allowance(&self, owner_id: AccountId, spender_id: AccountId) -> U128 {
+        self.account_details_by_id
+            .get(&owner_id)
+            .and_then(|details| details.ft_allowances.get(&spender_id))
+            .filter(|allowance| !allowance.is_expired())
+            .map_or(U128(0), |allowance| U128(allowance.amount.as_yoctonear()))
+    }
+
+    /// Query for every allowance an owner has given, including expired ones.
+    ///
+    /// # Returns
+    ///
+    /// A vector of tuples containing the spender and their allowance.
+    pub fn get_ft_allowances(&self, owner_id: AccountId) -> Vec<(AccountId, FtAllowance)> {
+        self.account_details_by_id
+            .get(&owner_id)
+            .map(|details| {
+                details
+                    .ft_allowances
+                    .iter()
+                    .map(|(spender_id, allowance)| (spender_id.clone(), allowance.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
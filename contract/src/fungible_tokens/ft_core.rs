@@ -1,5 +1,21 @@
+use near_sdk::{assert_one_yocto, ext_contract, Gas, PromiseError, PromiseOrValue};
+
 use crate::*;
 
+const GAS_FOR_RESOLVE_FT_TRANSFER: Gas = Gas::from_tgas(10); // 10 TGas = 10^13 gas units
+const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas::from_tgas(30); // 30 TGas = 3 * 10^13 gas units
+
+#[allow(dead_code)]
+#[ext_contract(ext_ft_receiver)]
+pub trait FungibleTokenReceiver {
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128>;
+}
+
 #[near]
 impl Contract {
     /// Allows an admin to mint an amount of tokens to a specified account ID.
@@ -28,19 +44,19 @@ impl Contract {
         );
     }
 
-    /// Allows a user to transfer tokens to another account or purchase items from a vendor.
+    /// Transfers tokens to another account. This follows NEP-141 while still letting attendees
+    /// transfer with the ticket key added to this contract.
     ///
-    /// This function facilitates the transfer of tokens between users. If a memo is provided, it specifies
-    /// a list of items to purchase from a vendor, and the user's tokens will be transferred to the vendor
-    /// accordingly, provided the user has sufficient tokens. If no memo is specified, the user can simply
-    /// transfer tokens to another account. The receiving account must either be a valid vendor or a sub-account
-    /// of this contract.
+    /// If the call is signed with a ticket key, the sender is the ticket's account, no deposit is
+    /// needed (access keys can't attach one) and the receiver must be a sub-account of this
+    /// contract. Otherwise the sender is the predecessor, exactly 1 yoctoNEAR must be attached and
+    /// the receiver must be registered through `storage_deposit`.
     ///
     /// # Arguments
     ///
     /// * `receiver_id` - The account ID of the receiver.
-    /// * `memo` - An optional string memo that specifies the items to purchase.
-    /// * `amount` - An optional amount of tokens to transfer.
+    /// * `amount` - The amount of tokens to transfer.
+    /// * `memo` - An optional memo included in the transfer event.
     ///
     /// # Returns
     ///
@@ -48,31 +64,37 @@ impl Contract {
     ///
     /// # Panics
     ///
-    /// Panics if the memo is invalid or if the receiver ID is not valid when no memo is provided.
+    /// Panics if the receiver ID is not valid, if the deposit is missing or if the sender doesn't
+    /// have enough tokens.
+    #[payable]
     #[handle_result]
     pub fn ft_transfer(
         &mut self,
         receiver_id: AccountId,
-        amount: NearToken,
-    ) -> Result<NearToken, String> {
+        amount: U128,
+        memo: Option<String>,
+    ) -> Result<U128, String> {
         self.assert_no_freeze();
-        // Tested: https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist=54a4a26cf62b44a178286431fe10e7f4
-        require!(
-            receiver_id
-                .to_string()
-                .ends_with(env::current_account_id().as_str()),
-            "Invalid receiver ID"
-        );
+        if env::predecessor_account_id() == env::current_account_id() {
+            // Tested: https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&gist=54a4a26cf62b44a178286431fe10e7f4
+            require!(
+                receiver_id
+                    .to_string()
+                    .ends_with(env::current_account_id().as_str()),
+                "Invalid receiver ID"
+            );
+        }
+        let sender_id = self.ft_caller_id();
 
         // Transfer the tokens
-        let sender_id = self.caller_id_by_signing_pk();
-        self.internal_ft_transfer(&sender_id, &receiver_id, amount, false);
+        let amount_to_transfer = NearToken::from_yoctonear(amount.0);
+        self.internal_ft_transfer(&sender_id, &receiver_id, amount_to_transfer, false, memo);
 
         // Record the transfer transaction
         self.add_transaction(TransactionType::Transfer {
             sender_id: sender_id.clone(),
             receiver_id: receiver_id.clone(),
-            amount,
+            amount: amount_to_transfer,
             timestamp: env::block_timestamp(),
         });
         self.total_transactions += 1;
@@ -80,6 +102,148 @@ impl Contract {
         Ok(amount)
     }
 
+    /// Transfers tokens to a contract and calls `ft_on_transfer` on it as described in NEP-141.
+    /// Any tokens the receiver doesn't use are refunded in `ft_resolve_transfer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver_id` - The contract receiving the tokens.
+    /// * `amount` - The amount of tokens to transfer.
+    /// * `memo` - An optional memo included in the transfer event.
+    /// * `msg` - The message passed to the receiver's `ft_on_transfer`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a promise that resolves to the amount of tokens the receiver used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if exactly 1 yoctoNEAR isn't attached, if not enough gas is attached, if the
+    /// receiver isn't registered or if the sender doesn't have enough tokens.
+    #[payable]
+    pub fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        assert_one_yocto();
+        self.assert_no_freeze();
+        require!(
+            env::prepaid_gas() > GAS_FOR_FT_TRANSFER_CALL,
+            "More gas is required"
+        );
+        let sender_id = env::predecessor_account_id();
+
+        let amount_to_transfer = NearToken::from_yoctonear(amount.0);
+        self.internal_ft_transfer(&sender_id, &receiver_id, amount_to_transfer, false, memo);
+        self.add_transaction(TransactionType::Transfer {
+            sender_id: sender_id.clone(),
+            receiver_id: receiver_id.clone(),
+            amount: amount_to_transfer,
+            timestamp: env::block_timestamp(),
+        });
+        self.total_transactions += 1;
+
+        ext_ft_receiver::ext(receiver_id.clone())
+            .with_unused_gas_weight(1)
+            .ft_on_transfer(sender_id.clone(), amount, msg)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_FT_TRANSFER)
+                    .with_unused_gas_weight(0)
+                    .ft_resolve_transfer(sender_id, receiver_id, amount),
+            )
+            .into()
+    }
+
+    /// Resolves `ft_transfer_call` by refunding the tokens the receiver didn't use. If the
+    /// receiver call failed, the full amount is refunded. Refunds are limited to the receiver's
+    /// balance and are burned if the sender no longer exists.
+    ///
+    /// # Returns
+    ///
+    /// Returns the amount of tokens the receiver used.
+    #[private]
+    pub fn ft_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        #[callback_result] unused_amount: Result<U128, PromiseError>,
+    ) -> U128 {
+        let unused_amount = unused_amount.map_or(amount.0, |unused| unused.0.min(amount.0));
+        if unused_amount == 0 {
+            return amount;
+        }
+
+        let receiver_balance = self.ft_balance_of(receiver_id.clone()).as_yoctonear();
+        let refund = NearToken::from_yoctonear(unused_amount.min(receiver_balance));
+        if refund.is_zero() {
+            return amount;
+        }
+        if self.account_details_by_id.contains_key(&sender_id) {
+            self.internal_ft_withdraw(&receiver_id, refund);
+            self.internal_ft_deposit(&sender_id, refund, false);
+            self.internal_record_ft_transfer(
+                &receiver_id,
+                &sender_id,
+                refund,
+                Some("refund".to_string()),
+            );
+        } else {
+            self.internal_ft_burn(&receiver_id, refund, Some("refund".to_string()));
+        }
+
+        U128(amount.0 - refund.as_yoctonear())
+    }
+
+    /// Burns tokens from the caller's balance, for example when a vendor takes tokens out of
+    /// circulation or an attendee redeems them at the end of the event.
+    ///
+    /// If the call is signed with a ticket key, the tokens are burned from the ticket's account
+    /// and no deposit is needed. Otherwise they are burned from the predecessor and exactly 1
+    /// yoctoNEAR must be attached.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount of tokens to burn.
+    /// * `memo` - An optional memo included in the burn event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the contract is frozen, if the deposit is missing or if the caller doesn't have
+    /// enough tokens.
+    #[payable]
+    pub fn ft_burn(&mut self, amount: U128, memo: Option<String>) {
+        self.assert_no_freeze();
+        let account_id = self.ft_caller_id();
+
+        self.internal_ft_burn(&account_id, NearToken::from_yoctonear(amount.0), memo);
+        self.total_transactions += 1;
+    }
+
+    /// Allows an admin to burn tokens from any account.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The account to burn the tokens from.
+    /// * `amount` - The amount of tokens to burn.
+    /// * `memo` - An optional memo included in the burn event.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the contract is frozen, if the caller is not an admin or if the account doesn't
+    /// have enough tokens.
+    pub fn ft_burn_from(&mut self, account_id: AccountId, amount: U128, memo: Option<String>) {
+        self.assert_admin();
+        self.assert_no_freeze();
+
+        self.internal_ft_burn(&account_id, NearToken::from_yoctonear(amount.0), memo);
+        self.total_transactions += 1;
+    }
+
     /// Queries for the total amount of tokens currently circulating.
     ///
     /// # Returns
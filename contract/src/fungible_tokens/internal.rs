@@ -142,6 +142,49 @@ impl Contract {
         }
     }
 
+    /// Internal method for burning FTs from an account, reducing the total supply.
+    pub(crate) fn internal_ft_burn(
+        &mut self,
+        account_id: &AccountId,
+        amount: NearToken,
+        memo: Option<String>,
+    ) {
+        require!(
+            amount.gt(&NearToken::from_yoctonear(0)),
+            "The amount should be a positive number"
+        );
+        self.internal_ft_withdraw(account_id, amount);
+        self.internal_ft_burn_supply(account_id, amount, memo);
+    }
+
+    /// Internal method for burning FTs that are no longer held in any account's balance, such as
+    /// a drop's budget, reducing the total supply.
+    pub(crate) fn internal_ft_burn_supply(
+        &mut self,
+        owner_id: &AccountId,
+        amount: NearToken,
+        memo: Option<String>,
+    ) {
+        self.ft_total_supply = self
+            .ft_total_supply
+            .checked_sub(amount)
+            .expect("Total supply underflow");
+
+        // Log the burn event
+        env::log_str(
+            &EventLog {
+                standard: FT_STANDARD_NAME.to_string(),
+                version: FT_METADATA_SPEC.to_string(),
+                event: EventLogVariant::FtBurn(FtBurnLog {
+                    owner_id: owner_id.to_string(),
+                    amount: amount.as_yoctonear().to_string(),
+                    memo,
+                }),
+            }
+            .to_string(),
+        );
+    }
+
     /// Internal method for performing a transfer of FTs from one account to another.
     pub(crate) fn internal_ft_transfer(
         &mut self,
@@ -149,6 +192,7 @@ impl Contract {
         receiver_id: &AccountId,
         amount: NearToken,
         add_to_leaderboard: bool,
+        memo: Option<String>,
     ) {
         // Ensure the sender can't transfer to themselves
         require!(
@@ -165,6 +209,17 @@ impl Contract {
         self.internal_ft_withdraw(sender_id, amount);
         self.internal_ft_deposit(receiver_id, amount, add_to_leaderboard);
 
+        self.internal_record_ft_transfer(sender_id, receiver_id, amount, memo);
+    }
+
+    /// Internal method for tracking and logging a transfer of FTs once the balances have been updated.
+    pub(crate) fn internal_record_ft_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: NearToken,
+        memo: Option<String>,
+    ) {
         self.total_tokens_transferred = self
             .total_tokens_transferred
             .checked_add(amount)
@@ -179,7 +234,7 @@ impl Contract {
                     old_owner_id: sender_id.to_string(),
                     new_owner_id: receiver_id.to_string(),
                     amount: amount.as_yoctonear().to_string(),
-                    memo: None,
+                    memo,
                 }),
             }
             .to_string(),
@@ -1,5 +1,8 @@
+pub mod allowance;
 pub mod ft_core;
 pub mod internal;
 pub mod metadata;
+pub mod storage;
 
+pub use allowance::*;
 pub use metadata::*;
\ No newline at end of file
@@ -0,0 +1,106 @@
+use near_sdk::Promise;
+
+use crate::*;
+
+/// Upper bound on the bytes used to register an account for the fungible token.
+pub const ACCOUNT_REGISTRATION_STORAGE: u64 = 1000;
+
+/// NEP-145 storage balance of an account.
+#[near(serializers = [json])]
+pub struct StorageBalance {
+    pub total: NearToken,
+    pub available: NearToken,
+}
+
+/// NEP-145 storage balance bounds. Registering only ever costs the minimum.
+#[near(serializers = [json])]
+pub struct StorageBalanceBounds {
+    pub min: NearToken,
+    pub max: Option<NearToken>,
+}
+
+#[near]
+impl Contract {
+    /// Registers an account so that it can receive tokens through the NEP-141 interface.
+    /// Accounts created by this contract are already registered. Any deposit above what the
+    /// account's storage balance can hold is refunded.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The account to register. Defaults to the predecessor.
+    /// * `registration_only` - Whether to refund everything above the minimum storage balance.
+    ///   Accounts that are already registered get their whole deposit back.
+    ///
+    /// # Returns
+    ///
+    /// Returns the storage balance of the account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the account isn't registered and the deposit doesn't cover registration.
+    #[payable]
+    pub fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let deposit = env::attached_deposit();
+        let bounds = self.storage_balance_bounds();
+        let registration_cost = bounds.min;
+
+        let refund = if self.account_details_by_id.contains_key(&account_id) {
+            near_sdk::log!("The account is already registered, refunding the deposit");
+            deposit
+        } else {
+            require!(
+                deposit >= registration_cost,
+                "The attached deposit is less than the minimum storage balance"
+            );
+            self.account_details_by_id
+                .insert(account_id.clone(), AccountDetails::new(&account_id));
+
+            // The storage balance can hold up to the maximum unless only registering
+            let max_balance = if registration_only.unwrap_or(false) {
+                registration_cost
+            } else {
+                bounds.max.unwrap_or(deposit)
+            };
+            deposit.saturating_sub(max_balance)
+        };
+
+        if !refund.is_zero() {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+
+        StorageBalance {
+            total: registration_cost,
+            available: NearToken::from_yoctonear(0),
+        }
+    }
+
+    /// Query for the storage balance bounds as described in NEP-145.
+    pub fn storage_balance_bounds(&self) -> StorageBalanceBounds {
+        let registration_cost = env::storage_byte_cost()
+            .checked_mul(ACCOUNT_REGISTRATION_STORAGE as u128)
+            .expect("invalid storage cost");
+        StorageBalanceBounds {
+            min: registration_cost,
+            max: Some(registration_cost),
+        }
+    }
+
+    /// Query for the storage balance of an account as described in NEP-145.
+    ///
+    /// # Returns
+    ///
+    /// Returns the storage balance if the account is registered, otherwise `None`.
+    pub fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        self.account_details_by_id
+            .contains_key(&account_id)
+            .then(|| StorageBalance {
+                total: self.storage_balance_bounds().min,
+                available: NearToken::from_yoctonear(0),
+            })
+    }
+}
@@ -1,3 +1,5 @@
+use near_sdk::assert_one_yocto;
+
 use crate::*;
 
 /// Used to generate a unique prefix in our storage collections (this is to avoid data collisions)
@@ -47,6 +49,14 @@ impl Contract {
         self.assert_role(AccountStatus::is_ticket_adder, "ticket adder")
     }
 
+    pub(crate) fn assert_vendor(&self) -> AccountId {
+        self.assert_role(AccountStatus::is_vendor, "vendor")
+    }
+
+    pub(crate) fn assert_scanner(&self) -> AccountId {
+        self.assert_role(AccountStatus::is_scanner, "scanner")
+    }
+
     pub(crate) fn assert_no_freeze(&self) {
         require!(
             !self.is_contract_frozen,
@@ -72,6 +82,18 @@ impl Contract {
             .unwrap_or_else(env::predecessor_account_id) // Use the predecessor account ID if not found
     }
 
+    /// Retrieves the account ID calling a fungible token method. Calls signed with a ticket key
+    /// act on behalf of the ticket's account since access keys can't attach a deposit, while any
+    /// other caller must attach exactly 1 yoctoNEAR as required by NEP-141.
+    pub(crate) fn ft_caller_id(&self) -> AccountId {
+        if env::predecessor_account_id() == env::current_account_id() {
+            self.caller_id_by_signing_pk()
+        } else {
+            assert_one_yocto();
+            env::predecessor_account_id()
+        }
+    }
+
     /// Ensures that the caller is the contract account's full access key.
     ///
     /// # Panics
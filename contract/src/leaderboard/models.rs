@@ -14,4 +14,10 @@ pub enum TransactionType {
         amount: NearToken,
         timestamp: u64,
     },
+    Purchase {
+        buyer_id: AccountId,
+        vendor_id: AccountId,
+        amount: NearToken,
+        timestamp: u64,
+    },
 }
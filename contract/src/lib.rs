@@ -1,14 +1,16 @@
 use std::collections::HashMap;
 
-use near_sdk::json_types::{Base64VecU8, U128};
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{Base58CryptoHash, Base64VecU8, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::store::{IterableMap, IterableSet, LookupMap};
 use near_sdk::{
-    env, near, require, AccountId, BorshStorageKey, CryptoHash, NearToken, PanicOnDefault,
-    PublicKey,
+    env, near, require, AccountId, BorshStorageKey, CryptoHash, CurveType, NearToken,
+    PanicOnDefault, PublicKey,
 };
 
 mod cleanup;
+mod consent;
 mod drops;
 mod events;
 mod ext_database;
@@ -20,7 +22,13 @@ mod models;
 mod multichain;
 mod non_fungible_tokens;
 mod tickets;
+mod vendors;
 
+#[cfg(test)]
+mod tests;
+
+use cleanup::*;
+use consent::*;
 use drops::*;
 use events::*;
 use fungible_tokens::*;
@@ -29,16 +37,27 @@ use leaderboard::*;
 use models::*;
 use multichain::*;
 use non_fungible_tokens::*;
+use vendors::*;
 
 // ------------------------ Access Key Method Names ------------------------ //
-pub const ATTENDEE_KEY_METHOD_NAMES: &str = "scan_ticket,create_account,claim_drop,ft_transfer";
+pub const ATTENDEE_KEY_METHOD_NAMES: &str =
+    "scan_ticket,create_account,claim_drop,ft_transfer,retry_multichain_mint,transfer_ticket,reserve_account_name,grant_metadata_access,revoke_metadata_access,offboard,purchase_items,ft_burn,ft_transfer_from";
 pub const SPONSOR_KEY_METHOD_NAMES: &str =
-    "create_token_drop,create_nft_drop,delete_drop,ft_transfer,create_multichain_drop";
+    "create_token_drop,create_nft_drop,delete_drop,ft_transfer,create_multichain_drop,update_drop_config,update_drop,create_drops,ft_approve,ft_revoke,ft_transfer_from";
 pub const ADMIN_KEY_METHOD_NAMES: &str = "";
 pub const DATA_SETTER_KEY_METHOD_NAMES: &str = "set_alerts,set_agenda";
 pub const TICKET_ADDER_KEY_METHOD_NAMES: &str = "add_tickets";
+pub const SCANNER_KEY_METHOD_NAMES: &str = "scan_ticket_by_staff";
+pub const VENDOR_KEY_METHOD_NAMES: &str =
+    "set_vendor_items,remove_vendor_items,ft_transfer,ft_burn";
 
 pub const DROP_DELIMITER: &str = "||";
+// Maximum number of ticket keys that can be refreshed in a single `refresh_ticket_keys` call
+pub const MAX_KEYS_PER_REFRESH: u64 = 50;
+// Maximum number of drops that can be created in a single `create_drops` call
+pub const MAX_DROPS_PER_BATCH: usize = 50;
+// Session that tickets are scanned into if none is specified
+pub const DEFAULT_SESSION: &str = "general";
 
 #[near(contract_state, serializers = [borsh])]
 #[derive(PanicOnDefault)]
@@ -61,8 +80,23 @@ pub struct Contract {
     // ------------------------ Drops -------------------------------------- //
     pub drop_by_id: IterableMap<DropId, DropData>, // clearable
 
+    // ------------------------ Multichain --------------------------------- //
+    pub mpc_contract: AccountId,
+    pub mpc_sign_deposit: NearToken,
+
     // ------------------------ Account Factory ---------------------------- //
     pub ticket_data_by_id: IterableMap<DropId, TicketType>, // clearable
+    pub ticket_stats_by_id: IterableMap<DropId, TicketTypeStats>, // clearable
+    pub reserved_account_names: IterableSet<AccountId>,     // clearable
+    pub wallet_contracts: IterableMap<String, WalletContract>, // clearable
+    pub wallet_code_by_version: LookupMap<String, Vec<u8>>, // clearable
+    pub default_wallet_version: Option<String>,
+
+    // ------------------------ Vendors ------------------------------------ //
+    pub vendor_info_by_id: IterableMap<AccountId, VendorInformation>, // clearable
+
+    // ------------------------ Offboarding -------------------------------- //
+    pub exchange_policy: ExchangePolicy,
 
     // ------------------------ Leaderboard ------------------------------------ //
     pub token_leaderboard: Vec<AccountId>,         // clearable
@@ -73,6 +107,10 @@ pub struct Contract {
 
     // ------------------------ Tickets ------------------------------------ //
     pub attendee_ticket_by_pk: IterableMap<PublicKey, AttendeeTicketInformation>, // clearable
+    pub check_ins_by_location: IterableMap<String, u64>,                          // clearable
+    pub attendance_by_session: IterableMap<String, u64>,                          // clearable
+    // Whether attendees can scan their own tickets instead of being scanned by check-in staff
+    pub allow_self_check_in: bool,
 
     // ------------------------ External Databases ------------------------- //
     pub agenda: String,        // clearable
@@ -81,26 +119,43 @@ pub struct Contract {
     pub agenda_timestamp: u64, // clearable
 }
 
-/// Data for each ticket such as the account status, starting balances, etc...
+/// Account details from before accounts could give out token allowances.
 #[near(serializers = [borsh])]
 pub struct OldAccountDetails {
     pub account_status: Option<AccountStatus>,
-
-    // ------------------------ Fungible Tokens ---------------------------- //
     pub ft_balance: NearToken,
-
-    // ------------------------ Leaderboard -------------------------------- //
     pub tokens_collected: NearToken,
-
-    // ------------------------ Drops -------------------------------------- //
     pub drops_created: IterableSet<DropId>,
-
-    /// Represents what the user has claimed for a specific drop. If scavenger IDs is none, the drop contains no scavengers
-    /// If scavengers is Some, the drop needs X amount of scavenger Ids to be found before the reward is allocated
-    /// On the frontend, query for drop data to see how many are needed and cross reference with this data structure to see
-    /// how many more IDs are left to be found
-    /// This is done to optimize the contract and not require duplicate data to be stored
+    pub drop_nonce: u64,
+    pub claim_nonce: u64,
     pub drops_claimed: IterableMap<DropId, ClaimedDropData>,
+    pub multichain_mints: IterableMap<DropId, MultichainMintStatus>,
+    pub metadata_grants_received: IterableMap<AccountId, MetadataGrant>,
+    pub metadata_grants_given: IterableSet<AccountId>,
+    pub offboarded_to: Option<AccountId>,
+}
+
+/// Rewrites every value of a map in the new layout under the given storage prefix.
+fn migrate_map<K, Old, New>(
+    mut old_map: IterableMap<K, Old>,
+    prefix: StorageKeys,
+    mut migrate_value: impl FnMut(&K, Old) -> New,
+) -> IterableMap<K, New>
+where
+    K: BorshSerialize + BorshDeserialize + Ord + Clone,
+    Old: BorshSerialize + BorshDeserialize,
+    New: BorshSerialize + BorshDeserialize,
+{
+    let entries: Vec<(K, Old)> = old_map.drain().collect();
+    // Remove the old entries from storage before the new ones are written under the same prefix
+    old_map.flush();
+
+    let mut new_map = IterableMap::new(prefix);
+    for (key, value) in entries {
+        let new_value = migrate_value(&key, value);
+        new_map.insert(key, new_value);
+    }
+    new_map
 }
 
 #[near(serializers=[borsh])]
@@ -123,8 +178,23 @@ pub struct OldState {
     // ------------------------ Drops -------------------------------------- //
     pub drop_by_id: IterableMap<DropId, DropData>, // clearable
 
+    // ------------------------ Multichain --------------------------------- //
+    pub mpc_contract: AccountId,
+    pub mpc_sign_deposit: NearToken,
+
     // ------------------------ Account Factory ---------------------------- //
     pub ticket_data_by_id: IterableMap<DropId, TicketType>, // clearable
+    pub ticket_stats_by_id: IterableMap<DropId, TicketTypeStats>, // clearable
+    pub reserved_account_names: IterableSet<AccountId>,     // clearable
+    pub wallet_contracts: IterableMap<String, WalletContract>, // clearable
+    pub wallet_code_by_version: LookupMap<String, Vec<u8>>, // clearable
+    pub default_wallet_version: Option<String>,
+
+    // ------------------------ Vendors ------------------------------------ //
+    pub vendor_info_by_id: IterableMap<AccountId, VendorInformation>, // clearable
+
+    // ------------------------ Offboarding -------------------------------- //
+    pub exchange_policy: ExchangePolicy,
 
     // ------------------------ Leaderboard ------------------------------------ //
     pub token_leaderboard: Vec<AccountId>,         // clearable
@@ -135,6 +205,9 @@ pub struct OldState {
 
     // ------------------------ Tickets ------------------------------------ //
     pub attendee_ticket_by_pk: IterableMap<PublicKey, AttendeeTicketInformation>, // clearable
+    pub check_ins_by_location: IterableMap<String, u64>,                          // clearable
+    pub attendance_by_session: IterableMap<String, u64>,                          // clearable
+    pub allow_self_check_in: bool,
 
     // ------------------------ External Databases ------------------------- //
     pub agenda: String,        // clearable
@@ -152,27 +225,26 @@ impl Contract {
         let old_state: OldState = env::state_read().expect("failed");
 
         // iterate through the state migrating it to the new version
-        let mut account_details_by_id: IterableMap<AccountId, AccountDetails> =
-            IterableMap::new(StorageKeys::AccountDetailsByIdNew);
-
-        for (account_id, old_account_details) in old_state.account_details_by_id.iter() {
-            let drops_created = IterableSet::new(StorageKeys::DropIdsByCreatorInner {
-                account_id_hash: hash_string(&account_id.to_string()),
-            });
-            let drops_claimed = IterableMap::new(StorageKeys::DropsClaimedByAccountInner {
-                account_id_hash: hash_string(&account_id.to_string()),
-            });
-
-            let new_account_details = AccountDetails {
-                account_status: old_account_details.account_status.clone(),
+        let account_details_by_id = migrate_map(
+            old_state.account_details_by_id,
+            StorageKeys::AccountDetailsByIdNew,
+            |account_id, old_account_details: OldAccountDetails| AccountDetails {
+                account_status: old_account_details.account_status,
                 ft_balance: old_account_details.ft_balance,
+                ft_allowances: IterableMap::new(StorageKeys::FtAllowancesInner {
+                    account_id_hash: hash_string(&account_id.to_string()),
+                }),
                 tokens_collected: old_account_details.tokens_collected,
-                drops_created,
-                drop_nonce: 0,
-                drops_claimed,
-            };
-            account_details_by_id.insert(account_id.clone(), new_account_details);
-        }
+                drops_created: old_account_details.drops_created,
+                drop_nonce: old_account_details.drop_nonce,
+                claim_nonce: old_account_details.claim_nonce,
+                drops_claimed: old_account_details.drops_claimed,
+                multichain_mints: old_account_details.multichain_mints,
+                metadata_grants_received: old_account_details.metadata_grants_received,
+                metadata_grants_given: old_account_details.metadata_grants_given,
+                offboarded_to: old_account_details.offboarded_to,
+            },
+        );
 
         // return the new state
         Self {
@@ -195,8 +267,20 @@ impl Contract {
             nft_tokens_by_id: old_state.nft_tokens_by_id,
             nft_metadata: old_state.nft_metadata,
             drop_by_id: old_state.drop_by_id,
+            mpc_contract: old_state.mpc_contract,
+            mpc_sign_deposit: old_state.mpc_sign_deposit,
             ticket_data_by_id: old_state.ticket_data_by_id,
+            ticket_stats_by_id: old_state.ticket_stats_by_id,
+            reserved_account_names: old_state.reserved_account_names,
+            wallet_contracts: old_state.wallet_contracts,
+            wallet_code_by_version: old_state.wallet_code_by_version,
+            default_wallet_version: old_state.default_wallet_version,
+            exchange_policy: old_state.exchange_policy,
+            vendor_info_by_id: old_state.vendor_info_by_id,
             attendee_ticket_by_pk: old_state.attendee_ticket_by_pk,
+            check_ins_by_location: old_state.check_ins_by_location,
+            attendance_by_session: old_state.attendance_by_session,
+            allow_self_check_in: old_state.allow_self_check_in,
         }
     }
     /// Initializes a new contract instance.
@@ -274,9 +358,21 @@ impl Contract {
             },
 
             drop_by_id: IterableMap::new(StorageKeys::DropById),
+            mpc_contract: DEFAULT_MPC_CONTRACT.parse().unwrap(),
+            mpc_sign_deposit: DEFAULT_MPC_SIGN_DEPOSIT,
 
             ticket_data_by_id,
+            ticket_stats_by_id: IterableMap::new(StorageKeys::TicketStatsById),
+            reserved_account_names: IterableSet::new(StorageKeys::ReservedAccountNames),
+            wallet_contracts: IterableMap::new(StorageKeys::WalletContracts),
+            wallet_code_by_version: LookupMap::new(StorageKeys::WalletCodeByVersion),
+            default_wallet_version: None,
+            exchange_policy: ExchangePolicy::Burn,
+            vendor_info_by_id: IterableMap::new(StorageKeys::VendorInfoById),
             attendee_ticket_by_pk: IterableMap::new(StorageKeys::AttendeeTicketInformation),
+            check_ins_by_location: IterableMap::new(StorageKeys::CheckInsByLocation),
+            attendance_by_session: IterableMap::new(StorageKeys::AttendanceBySession),
+            allow_self_check_in: false,
         }
     }
 
@@ -318,6 +414,7 @@ impl Contract {
             account_id: account_id.to_string(),
             ft_balance: account_details.ft_balance,
             account_status: account_details.account_status.clone(), // Clone account_status
+            offboarded_to: account_details.offboarded_to.clone(),
         }
     }
 
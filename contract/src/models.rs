@@ -19,6 +19,19 @@ pub enum StorageKeys {
     SeriesById,
     SeriesByIdInner { account_id_hash: CryptoHash },
     TokensById,
+    MultichainMintsByAccountInner { account_id_hash: CryptoHash },
+    CheckInsByLocation,
+    AttendanceBySession,
+    TicketStatsById,
+    MetadataGrantsReceivedInner { account_id_hash: CryptoHash },
+    MetadataGrantsGivenInner { account_id_hash: CryptoHash },
+    ReservedAccountNames,
+    WalletContracts,
+    WalletCodeByVersion,
+    VendorInfoById,
+    VendorItemsInner { account_id_hash: CryptoHash },
+    VendorSalesInner { account_id_hash: CryptoHash },
+    FtAllowancesInner { account_id_hash: CryptoHash },
 }
 
 #[derive(Clone)]
@@ -29,6 +42,8 @@ pub enum AccountStatus {
     DataSetter,
     Admin,
     TicketAdder,
+    Scanner,
+    Vendor,
 }
 
 impl AccountStatus {
@@ -38,6 +53,8 @@ impl AccountStatus {
             AccountStatus::Sponsor => false,
             AccountStatus::DataSetter => false,
             AccountStatus::TicketAdder => false,
+            AccountStatus::Scanner => false,
+            AccountStatus::Vendor => false,
             AccountStatus::Admin => true,
         }
     }
@@ -48,6 +65,8 @@ impl AccountStatus {
             AccountStatus::Sponsor => true,
             AccountStatus::DataSetter => false,
             AccountStatus::TicketAdder => false,
+            AccountStatus::Scanner => false,
+            AccountStatus::Vendor => false,
             AccountStatus::Admin => true,
         }
     }
@@ -58,6 +77,8 @@ impl AccountStatus {
             AccountStatus::Sponsor => false,
             AccountStatus::DataSetter => true,
             AccountStatus::TicketAdder => false,
+            AccountStatus::Scanner => false,
+            AccountStatus::Vendor => false,
             AccountStatus::Admin => true,
         }
     }
@@ -68,9 +89,48 @@ impl AccountStatus {
             AccountStatus::Sponsor => false,
             AccountStatus::DataSetter => false,
             AccountStatus::TicketAdder => true,
+            AccountStatus::Scanner => false,
+            AccountStatus::Vendor => false,
             AccountStatus::Admin => true,
         }
     }
+
+    pub fn is_scanner(&self) -> bool {
+        match self {
+            AccountStatus::Basic => false,
+            AccountStatus::Sponsor => false,
+            AccountStatus::DataSetter => false,
+            AccountStatus::TicketAdder => false,
+            AccountStatus::Scanner => true,
+            AccountStatus::Vendor => false,
+            AccountStatus::Admin => true,
+        }
+    }
+
+    pub fn is_vendor(&self) -> bool {
+        match self {
+            AccountStatus::Basic => false,
+            AccountStatus::Sponsor => false,
+            AccountStatus::DataSetter => false,
+            AccountStatus::TicketAdder => false,
+            AccountStatus::Scanner => false,
+            AccountStatus::Vendor => true,
+            AccountStatus::Admin => true,
+        }
+    }
+
+    /// Methods that ticket keys belonging to this kind of account are allowed to call
+    pub fn key_method_names(&self) -> &'static str {
+        match self {
+            AccountStatus::Basic => ATTENDEE_KEY_METHOD_NAMES,
+            AccountStatus::Sponsor => SPONSOR_KEY_METHOD_NAMES,
+            AccountStatus::DataSetter => DATA_SETTER_KEY_METHOD_NAMES,
+            AccountStatus::TicketAdder => TICKET_ADDER_KEY_METHOD_NAMES,
+            AccountStatus::Scanner => SCANNER_KEY_METHOD_NAMES,
+            AccountStatus::Vendor => VENDOR_KEY_METHOD_NAMES,
+            AccountStatus::Admin => ADMIN_KEY_METHOD_NAMES,
+        }
+    }
 }
 
 /// Data for each ticket such as the account status, starting balances, etc...
@@ -82,16 +142,47 @@ pub struct ExtAccountDetails {
 
     // ------------------------ Account Information ------------------------- //
     pub account_status: Option<AccountStatus>,
+    pub offboarded_to: Option<AccountId>,
 }
 
 /// Data for each ticket such as the account status, starting balances, etc...
 #[derive(Clone)]
 #[near(serializers = [json, borsh])]
 pub struct AttendeeTicketInformation {
-    pub has_scanned: bool,
+    // Every day or session the ticket has been scanned into
+    pub check_ins: Vec<TicketCheckIn>,
+    // Account name held for the ticket until its account is created
+    pub reserved_account_id: Option<AccountId>,
     pub drop_id: Option<DropId>,
     pub account_id: Option<AccountId>,
     pub metadata: Option<String>,
+    // Tokens and NFTs received by an account whose creation failed. They're handed over to the
+    // next account created for the ticket
+    pub held_tokens: NearToken,
+    pub held_nft_ids: Vec<TokenId>,
+}
+
+impl AttendeeTicketInformation {
+    pub fn has_scanned(&self) -> bool {
+        !self.check_ins.is_empty()
+    }
+
+    pub fn has_checked_in(&self, session: &str) -> bool {
+        self.check_ins
+            .iter()
+            .any(|check_in| check_in.session == session)
+    }
+}
+
+/// Record of a ticket being scanned into a day or session.
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub struct TicketCheckIn {
+    pub session: String,
+    pub scanned_at: u64,
+    // Set when the ticket was scanned in by a staff member rather than by the attendee
+    pub scanned_by: Option<AccountId>,
+    pub location: Option<String>,
 }
 
 /// Data for each ticket such as the account status, starting balances, etc...
@@ -101,6 +192,47 @@ pub struct TicketType {
     pub starting_near_balance: NearToken,
     pub starting_token_balance: NearToken,
     pub account_type: AccountStatus,
+    // The days or sessions the ticket grants access to. If none, the ticket grants access to all of them
+    pub sessions: Option<Vec<String>>,
+    // Maximum number of tickets that can be issued. If none, there is no limit
+    pub max_supply: Option<u64>,
+    // Wallet contract deployed to accounts created with this ticket. If none, the default wallet is used
+    pub wallet_version: Option<String>,
+}
+
+impl TicketType {
+    pub fn grants_access_to(&self, session: &str) -> bool {
+        self.sessions
+            .as_ref()
+            .is_none_or(|sessions| sessions.iter().any(|s| s == session))
+    }
+
+    pub fn has_supply_for(&self, num_issued: u64) -> bool {
+        self.max_supply
+            .is_none_or(|max_supply| num_issued <= max_supply)
+    }
+}
+
+/// Running totals for each ticket type used by the registration dashboard.
+#[derive(Clone, Default)]
+#[near(serializers = [json, borsh])]
+pub struct TicketTypeStats {
+    pub issued: u64,
+    pub scanned: u64,
+    pub accounts_created: u64,
+}
+
+/// A wallet contract that can be deployed to created accounts. The WASM itself is stored
+/// separately so that it is only loaded when an account is created.
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub struct WalletContract {
+    pub code_hash: Base58CryptoHash,
+    pub code_size: u64,
+    // Method called on the account right after the wallet is deployed along with its JSON arguments
+    pub init_method: String,
+    pub init_args: String,
+    pub uploaded_at: u64,
 }
 
 /// Data for each ticket such as the account status, starting balances, etc...
@@ -110,6 +242,8 @@ pub struct AccountDetails {
 
     // ------------------------ Fungible Tokens ---------------------------- //
     pub ft_balance: NearToken,
+    /// Tokens other accounts are allowed to transfer out of this account
+    pub ft_allowances: IterableMap<AccountId, FtAllowance>,
 
     // ------------------------ Leaderboard -------------------------------- //
     pub tokens_collected: NearToken,
@@ -117,6 +251,9 @@ pub struct AccountDetails {
     // ------------------------ Drops -------------------------------------- //
     pub drops_created: IterableSet<DropId>,
     pub drop_nonce: u64,
+    /// Nonce that must be included in the next claim signature. It is bumped with every claim so
+    /// that a signature can't be replayed.
+    pub claim_nonce: u64,
 
     /// Represents what the user has claimed for a specific drop. If scavenger IDs is none, the drop contains no scavengers
     /// If scavengers is Some, the drop needs X amount of scavenger Ids to be found before the reward is allocated
@@ -124,6 +261,19 @@ pub struct AccountDetails {
     /// how many more IDs are left to be found
     /// This is done to optimize the contract and not require duplicate data to be stored
     pub drops_claimed: IterableMap<DropId, ClaimedDropData>,
+
+    // ------------------------ Multichain --------------------------------- //
+    pub multichain_mints: IterableMap<DropId, MultichainMintStatus>,
+
+    // ------------------------ Metadata Consent --------------------------- //
+    /// Metadata that attendees have shared with this account if it's a sponsor
+    pub metadata_grants_received: IterableMap<AccountId, MetadataGrant>,
+    /// Sponsors this account has shared its metadata with
+    pub metadata_grants_given: IterableSet<AccountId>,
+
+    // ------------------------ Offboarding -------------------------------- //
+    /// Account the NFTs and exchanged tokens were sent to once the account offboarded
+    pub offboarded_to: Option<AccountId>,
 }
 
 impl AccountDetails {
@@ -134,14 +284,32 @@ impl AccountDetails {
         let drops_claimed = IterableMap::new(StorageKeys::DropsClaimedByAccountInner {
             account_id_hash: hash_string(&account_id.to_string()),
         });
+        let multichain_mints = IterableMap::new(StorageKeys::MultichainMintsByAccountInner {
+            account_id_hash: hash_string(&account_id.to_string()),
+        });
+        let metadata_grants_received = IterableMap::new(StorageKeys::MetadataGrantsReceivedInner {
+            account_id_hash: hash_string(&account_id.to_string()),
+        });
+        let metadata_grants_given = IterableSet::new(StorageKeys::MetadataGrantsGivenInner {
+            account_id_hash: hash_string(&account_id.to_string()),
+        });
+        let ft_allowances = IterableMap::new(StorageKeys::FtAllowancesInner {
+            account_id_hash: hash_string(&account_id.to_string()),
+        });
 
         AccountDetails {
             ft_balance: NearToken::from_yoctonear(0),
+            ft_allowances,
             tokens_collected: NearToken::from_yoctonear(0),
             drop_nonce: 0,
+            claim_nonce: 0,
             account_status: None,
             drops_created,
             drops_claimed,
+            multichain_mints,
+            metadata_grants_received,
+            metadata_grants_given,
+            offboarded_to: None,
         }
     }
 }
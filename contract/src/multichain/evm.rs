@@ -0,0 +1,75 @@
+use crate::*;
+
+/// Name of the EIP-712 domain that the destination NFT contracts verify mint signatures against
+pub const EIP712_DOMAIN_NAME: &str = "Keypom POAP";
+/// Version of the EIP-712 domain that the destination NFT contracts verify mint signatures against
+pub const EIP712_DOMAIN_VERSION: &str = "1";
+
+const EIP712_DOMAIN_TYPE: &str =
+    "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+const MINT_TYPE: &str = "Mint(uint256 seriesId,string receiverId)";
+
+/// Parses a `0x` prefixed, 20 byte hex EVM address.
+///
+/// # Returns
+///
+/// Returns the address bytes, or `None` if the string is not a valid address.
+pub(crate) fn parse_evm_address(address: &str) -> Option<[u8; 20]> {
+    let hex = address.strip_prefix("0x")?.as_bytes();
+    if hex.len() != 40 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 20];
+    for (i, pair) in hex.chunks(2).enumerate() {
+        let high = (pair[0] as char).to_digit(16)?;
+        let low = (pair[1] as char).to_digit(16)?;
+        bytes[i] = (high * 16 + low) as u8;
+    }
+    Some(bytes)
+}
+
+/// Builds the EIP-712 digest of a `Mint(uint256 seriesId,string receiverId)` message for an NFT
+/// contract on an EVM chain. The domain binds the signature to the chain ID and the contract so
+/// it can't be replayed anywhere else.
+///
+/// # Panics
+///
+/// Panics if the contract ID is not a valid EVM address.
+pub(crate) fn eip712_mint_digest(
+    chain_id: u64,
+    contract_id: &str,
+    series_id: SeriesId,
+    receiver_id: &AccountId,
+) -> [u8; 32] {
+    let verifying_contract =
+        parse_evm_address(contract_id).expect("Contract ID is not a valid EVM address");
+
+    let mut domain = Vec::with_capacity(32 * 5);
+    domain.extend_from_slice(&env::keccak256_array(EIP712_DOMAIN_TYPE.as_bytes()));
+    domain.extend_from_slice(&env::keccak256_array(EIP712_DOMAIN_NAME.as_bytes()));
+    domain.extend_from_slice(&env::keccak256_array(EIP712_DOMAIN_VERSION.as_bytes()));
+    domain.extend_from_slice(&abi_encode_uint(chain_id as u128));
+    domain.extend_from_slice(&[0u8; 12]);
+    domain.extend_from_slice(&verifying_contract);
+    let domain_separator = env::keccak256_array(&domain);
+
+    let mut mint = Vec::with_capacity(32 * 3);
+    mint.extend_from_slice(&env::keccak256_array(MINT_TYPE.as_bytes()));
+    mint.extend_from_slice(&abi_encode_uint(series_id as u128));
+    mint.extend_from_slice(&env::keccak256_array(receiver_id.as_bytes()));
+    let struct_hash = env::keccak256_array(&mint);
+
+    let mut message = Vec::with_capacity(2 + 32 * 2);
+    message.extend_from_slice(b"\x19\x01");
+    message.extend_from_slice(&domain_separator);
+    message.extend_from_slice(&struct_hash);
+    env::keccak256_array(&message)
+}
+
+/// ABI encodes an unsigned integer as a big endian `uint256` word.
+fn abi_encode_uint(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
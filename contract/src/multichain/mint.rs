@@ -1,21 +1,244 @@
+use near_sdk::{ext_contract, Gas, PromiseError};
+
 use crate::*;
 
+/// The MPC signer contract used until an admin sets a different one
+pub const DEFAULT_MPC_CONTRACT: &str = "v1.signer-prod.testnet";
+/// The version of the MPC key used to sign multichain mints
+pub const MPC_KEY_VERSION: u32 = 0;
+/// Deposit attached to each sign request until an admin sets a different one. This is the
+/// signer's fee when it has no pending requests, the fee grows with the signer's load.
+pub const DEFAULT_MPC_SIGN_DEPOSIT: NearToken = NearToken::from_yoctonear(1);
+/// How long a signature request can stay pending before it can be retried, in nanoseconds
+pub const MULTICHAIN_MINT_TIMEOUT: u64 = 10 * 60 * 1_000_000_000;
+
+const GAS_FOR_RESOLVE_MULTICHAIN_MINT: Gas = Gas::from_tgas(10); // 10 TGas = 10^13 gas units
+
 // Represent Token Information
 #[derive(Clone, Debug)]
 #[near(serializers = [json, borsh])]
 pub struct MultichainMetadata {
     // FOR MPC
     pub chain_id: u64,
-    // Receiving NFT contract on external chain
+    // Receiving NFT contract on external chain, as a 0x prefixed hex address
     pub contract_id: String,
     // Arguments that I pass in to the NFT mint function call on external chain
     // **NEEDS TO HAVE BEEN CREATED ON THE NFT CONTRACT BEFORE CALLING CREATE DROP**
     pub series_id: SeriesId,
 }
 
+#[allow(dead_code)]
+#[ext_contract(ext_signer)]
+pub trait MpcSigner {
+    fn sign(&mut self, request: SignRequest) -> SignResult;
+}
+
 #[near]
 impl Contract {
-    pub fn handle_multichain_mint(&mut self, _data: &MultichainDropData) {
-        // Future implemntation: user passes in minting account nonce + gas info or constructed multichain transaction, call MPC, return signature from MPC
+    /// Records a pending multichain mint for the receiver and asks the MPC signer to sign the
+    /// mint payload for the destination chain.
+    ///
+    /// The payload is the EIP-712 digest of a `Mint(uint256 seriesId,string receiverId)` message
+    /// for the destination NFT contract and is signed with the key derived from the destination
+    /// chain ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the receiver already has a multichain mint for this drop.
+    pub(crate) fn handle_multichain_mint(
+        &mut self,
+        data: &MultichainDropData,
+        drop_id: &DropId,
+        receiver_id: &AccountId,
+    ) {
+        let mc_metadata = &data.mc_metadata;
+        let path = mc_metadata.chain_id.to_string();
+
+        let payload = MultichainMintPayload {
+            chain_id: mc_metadata.chain_id,
+            contract_id: mc_metadata.contract_id.clone(),
+            series_id: mc_metadata.series_id,
+            receiver_id: receiver_id.clone(),
+            path,
+            payload: eip712_mint_digest(
+                mc_metadata.chain_id,
+                &mc_metadata.contract_id,
+                mc_metadata.series_id,
+                receiver_id,
+            ),
+        };
+
+        let account_details = self
+            .account_details_by_id
+            .get_mut(receiver_id)
+            .expect("Receiver not found in map");
+        require!(
+            account_details
+                .multichain_mints
+                .insert(
+                    drop_id.clone(),
+                    MultichainMintStatus {
+                        state: MultichainMintState::Pending,
+                        payload: payload.clone(),
+                        signature: None,
+                        attempts: 1,
+                        requested_at: env::block_timestamp(),
+                    }
+                )
+                .is_none(),
+            "Multichain mint already exists for this drop"
+        );
+
+        self.internal_request_mpc_signature(receiver_id, drop_id, &payload);
+    }
+
+    /// Allows a claimer to request a new signature for a multichain mint that failed or has been
+    /// pending for longer than `MULTICHAIN_MINT_TIMEOUT`.
+    ///
+    /// # Arguments
+    ///
+    /// * `drop_id` - The ID of the multichain drop that was claimed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller has no multichain mint for the drop or if it can't be retried yet.
+    pub fn retry_multichain_mint(&mut self, drop_id: DropId) {
+        self.assert_no_freeze();
+        let receiver_id = self.caller_id_by_signing_pk();
+        self.internal_retry_multichain_mint(&receiver_id, &drop_id, false);
+        self.total_transactions += 1;
+    }
+
+    /// Allows an admin to request a new signature for any multichain mint that hasn't been signed
+    /// yet, for example one that is stuck as pending.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The account that claimed the multichain drop.
+    /// * `drop_id` - The ID of the multichain drop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin, if the account has no multichain mint for the drop
+    /// or if the mint has already been signed.
+    pub fn admin_retry_multichain_mint(&mut self, account_id: AccountId, drop_id: DropId) {
+        self.assert_admin();
+        self.internal_retry_multichain_mint(&account_id, &drop_id, true);
+    }
+
+    /// Marks a multichain mint as pending again and asks the MPC signer for a new signature.
+    fn internal_retry_multichain_mint(
+        &mut self,
+        receiver_id: &AccountId,
+        drop_id: &DropId,
+        is_admin: bool,
+    ) {
+        let mint = self
+            .account_details_by_id
+            .get_mut(receiver_id)
+            .and_then(|details| details.multichain_mints.get_mut(drop_id))
+            .expect("No multichain mint found for this drop");
+        if is_admin {
+            require!(
+                mint.state != MultichainMintState::Signed,
+                "Multichain mint has already been signed"
+            );
+        } else {
+            require!(
+                mint.is_retryable(),
+                "Only failed or timed out multichain mints can be retried"
+            );
+        }
+        mint.state = MultichainMintState::Pending;
+        mint.attempts += 1;
+        mint.requested_at = env::block_timestamp();
+        let payload = mint.payload.clone();
+
+        self.internal_request_mpc_signature(receiver_id, drop_id, &payload);
+    }
+
+    /// Asks the MPC signer to sign the mint payload and resolves the result into the receiver's
+    /// multichain mint status.
+    fn internal_request_mpc_signature(
+        &self,
+        receiver_id: &AccountId,
+        drop_id: &DropId,
+        payload: &MultichainMintPayload,
+    ) {
+        ext_signer::ext(self.mpc_contract.clone())
+            .with_attached_deposit(self.mpc_sign_deposit)
+            .with_unused_gas_weight(1)
+            .sign(SignRequest {
+                payload: payload.payload,
+                path: payload.path.clone(),
+                key_version: MPC_KEY_VERSION,
+            })
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_MULTICHAIN_MINT)
+                    .with_unused_gas_weight(0)
+                    .resolve_multichain_mint(receiver_id.clone(), drop_id.clone()),
+            );
+    }
+
+    /// Resolves the call to the MPC signer. On success the signature is stored so the frontend
+    /// can relay the mint to the destination chain, otherwise the mint is marked as failed.
+    ///
+    /// # Returns
+    ///
+    /// Returns the signature if the MPC signer succeeded, otherwise `None`.
+    #[private]
+    pub fn resolve_multichain_mint(
+        &mut self,
+        receiver_id: AccountId,
+        drop_id: DropId,
+        #[callback_result] result: Result<SignResult, PromiseError>,
+    ) -> Option<SignResult> {
+        // The account may have been cleared while the signature was being generated
+        let mint = self
+            .account_details_by_id
+            .get_mut(&receiver_id)
+            .and_then(|details| details.multichain_mints.get_mut(&drop_id))?;
+
+        match result {
+            Ok(signature) => {
+                mint.state = MultichainMintState::Signed;
+                mint.signature = Some(signature.clone());
+                Some(signature)
+            }
+            // A retried request may already have been signed
+            Err(_) if mint.state == MultichainMintState::Signed => None,
+            Err(_) => {
+                mint.state = MultichainMintState::Failed;
+                near_sdk::log!(
+                    "MPC signer failed for drop {} and receiver {} after {} attempt(s)",
+                    drop_id,
+                    receiver_id,
+                    mint.attempts
+                );
+                None
+            }
+        }
+    }
+
+    /// Allows an admin to change the MPC signer contract used for multichain mints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin.
+    pub fn set_mpc_contract(&mut self, mpc_contract: AccountId) {
+        self.assert_admin();
+        self.mpc_contract = mpc_contract;
+    }
+
+    /// Allows an admin to change the deposit attached to each request to the MPC signer. The
+    /// signer rejects requests whose deposit doesn't cover its current fee.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin.
+    pub fn set_mpc_sign_deposit(&mut self, sign_deposit: NearToken) {
+        self.assert_admin();
+        self.mpc_sign_deposit = sign_deposit;
     }
 }
@@ -1,3 +1,8 @@
+pub mod evm;
 pub mod mint;
+pub mod models;
+pub mod views;
 
-pub use mint::*;
\ No newline at end of file
+pub use evm::*;
+pub use mint::*;
+pub use models::*;
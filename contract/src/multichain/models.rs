@@ -0,0 +1,88 @@
+use crate::*;
+
+/// The request passed to the MPC signer contract's `sign` method.
+#[derive(Clone, Debug)]
+#[near(serializers = [json, borsh])]
+pub struct SignRequest {
+    // Hash of the message to be signed
+    pub payload: [u8; 32],
+    // Derivation path for the key that will sign the payload
+    pub path: String,
+    pub key_version: u32,
+}
+
+#[derive(Clone, Debug)]
+#[near(serializers = [json, borsh])]
+pub struct AffinePoint {
+    pub affine_point: String,
+}
+
+#[derive(Clone, Debug)]
+#[near(serializers = [json, borsh])]
+pub struct Scalar {
+    pub scalar: String,
+}
+
+/// The signature returned by the MPC signer contract.
+#[derive(Clone, Debug)]
+#[near(serializers = [json, borsh])]
+pub struct SignResult {
+    pub big_r: AffinePoint,
+    pub s: Scalar,
+    pub recovery_id: u8,
+}
+
+/// Everything the frontend needs in order to relay an NFT mint to the destination chain.
+#[derive(Clone, Debug)]
+#[near(serializers = [json, borsh])]
+pub struct MultichainMintPayload {
+    pub chain_id: u64,
+    pub contract_id: String,
+    pub series_id: SeriesId,
+    // NEAR account that claimed the drop. The NFT is minted to the address derived for it.
+    pub receiver_id: AccountId,
+    // Derivation path that was passed to the MPC signer
+    pub path: String,
+    // EIP-712 digest of the mint message that was signed
+    pub payload: [u8; 32],
+}
+
+/// The state of a multichain mint as it moves through the MPC signer.
+#[derive(Clone, Debug, PartialEq)]
+#[near(serializers = [json, borsh])]
+pub enum MultichainMintState {
+    // Waiting on the MPC signer to respond
+    Pending,
+    // Signature is available to be relayed to the destination chain
+    Signed,
+    // The MPC signer failed. The claimer can retry the mint.
+    Failed,
+}
+
+/// A multichain mint recorded for a claimer, keyed by drop ID in their account details.
+#[derive(Clone, Debug)]
+#[near(serializers = [json, borsh])]
+pub struct MultichainMintStatus {
+    pub state: MultichainMintState,
+    pub payload: MultichainMintPayload,
+    // Set once the MPC signer responds successfully
+    pub signature: Option<SignResult>,
+    // Number of times a signature has been requested from the MPC signer
+    pub attempts: u32,
+    // Block timestamp of the latest signature request
+    pub requested_at: u64,
+}
+
+impl MultichainMintStatus {
+    /// Whether the signature can be requested again: the last request failed, or it has been
+    /// pending for longer than the MPC signer should ever take to respond.
+    pub fn is_retryable(&self) -> bool {
+        match self.state {
+            MultichainMintState::Failed => true,
+            MultichainMintState::Pending => {
+                env::block_timestamp() > self.requested_at + MULTICHAIN_MINT_TIMEOUT
+            }
+            MultichainMintState::Signed => false,
+        }
+    }
+}
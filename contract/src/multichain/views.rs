@@ -0,0 +1,82 @@
+use crate::*;
+
+#[near]
+impl Contract {
+    /// Query for the MPC signer contract used for multichain mints.
+    pub fn get_mpc_contract(&self) -> AccountId {
+        self.mpc_contract.clone()
+    }
+
+    /// Query for the deposit attached to each request to the MPC signer.
+    pub fn get_mpc_sign_deposit(&self) -> NearToken {
+        self.mpc_sign_deposit
+    }
+
+    /// Query for the multichain mint recorded for an account and drop.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The account that claimed the multichain drop.
+    /// * `drop_id` - The ID of the multichain drop.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the `MultichainMintStatus` if found, otherwise `None`.
+    pub fn get_multichain_mint(
+        &self,
+        account_id: AccountId,
+        drop_id: DropId,
+    ) -> Option<MultichainMintStatus> {
+        self.account_details_by_id
+            .get(&account_id)
+            .and_then(|details| details.multichain_mints.get(&drop_id))
+            .cloned()
+    }
+
+    /// Query for all the multichain mints recorded for an account.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The account that claimed the multichain drops.
+    ///
+    /// # Returns
+    ///
+    /// A vector of drop IDs and their `MultichainMintStatus`.
+    pub fn get_multichain_mints_for_account(
+        &self,
+        account_id: AccountId,
+    ) -> Vec<(DropId, MultichainMintStatus)> {
+        self.account_details_by_id
+            .get(&account_id)
+            .map(|details| {
+                details
+                    .multichain_mints
+                    .iter()
+                    .map(|(drop_id, mint)| (drop_id.clone(), mint.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Query for the multichain mints for an account that are in a given state. Support staff
+    /// can use this to find failed mints that need to be retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `account_id` - The account that claimed the multichain drops.
+    /// * `state` - The state to filter by.
+    ///
+    /// # Returns
+    ///
+    /// A vector of drop IDs and their `MultichainMintStatus`.
+    pub fn get_multichain_mints_for_account_by_state(
+        &self,
+        account_id: AccountId,
+        state: MultichainMintState,
+    ) -> Vec<(DropId, MultichainMintStatus)> {
+        self.get_multichain_mints_for_account(account_id)
+            .into_iter()
+            .filter(|(_, mint)| mint.state == state)
+            .collect()
+    }
+}
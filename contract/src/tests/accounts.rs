@@ -0,0 +1,219 @@
+use near_sdk::mock::MockAction;
+use near_sdk::test_utils::get_created_receipts;
+use near_sdk::{PromiseResult, RuntimeFeesConfig};
+
+use super::*;
+use crate::tickets::add_tickets::KeyData;
+
+fn account_id() -> AccountId {
+    format!("alice.{}", contract_id()).parse().unwrap()
+}
+
+const STARTING_TOKENS: u128 = 100;
+
+/// Issues a ticket of a basic ticket type that starts with `STARTING_TOKENS` tokens.
+fn add_ticket(contract: &mut Contract, public_key: &PublicKey) {
+    contract.ticket_data_by_id.insert(
+        "ga".to_string(),
+        TicketType {
+            starting_near_balance: NearToken::from_yoctonear(0),
+            starting_token_balance: NearToken::from_yoctonear(STARTING_TOKENS),
+            account_type: AccountStatus::Basic,
+            sessions: None,
+            max_supply: None,
+            wallet_version: None,
+        },
+    );
+    set_caller(&admin_id());
+    contract.add_tickets(
+        "ga".to_string(),
+        vec![KeyData {
+            public_key: public_key.clone(),
+            metadata: None,
+        }],
+    );
+}
+
+/// Sets up the context for the account creation callback with the result of the creation batch.
+fn set_account_creation_result(result: PromiseResult) {
+    testing_env!(
+        VMContextBuilder::new()
+            .current_account_id(contract_id())
+            .predecessor_account_id(contract_id())
+            .signer_account_id(contract_id())
+            .build(),
+        near_sdk::test_vm_config(),
+        RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![result],
+    );
+}
+
+/// Scans the ticket and creates its account.
+fn create_scanned_account(contract: &mut Contract, ticket_pk: &PublicKey) {
+    set_caller(&admin_id());
+    contract.scan_ticket_by_staff(ticket_pk.clone(), "Gate A".to_string(), None);
+    set_ticket_signer(ticket_pk);
+    contract.create_account(account_id());
+}
+
+/// Code of the wallet deployed to the account by the receipts created so far, if any.
+fn deployed_wallet_code() -> Option<Vec<u8>> {
+    get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id == account_id())
+        .flat_map(|receipt| receipt.actions)
+        .find_map(|action| match action {
+            MockAction::DeployContract { code, .. } => Some(code),
+            _ => None,
+        })
+}
+
+fn upload_wallet(contract: &mut Contract, version: &str, set_as_default: bool) {
+    set_caller(&admin_id());
+    contract.upload_wallet_code(
+        version.to_string(),
+        Base64VecU8(version.as_bytes().to_vec()),
+        "new".to_string(),
+        None,
+        Some(set_as_default),
+    );
+}
+
+fn nft_owner(contract: &Contract, token_id: &str) -> AccountId {
+    contract
+        .nft_tokens_by_id
+        .get(token_id)
+        .unwrap()
+        .owner_id
+        .clone()
+}
+
+/// Creates an account for a scanned ticket whose creation then fails after the account received
+/// some tokens and an NFT on top of its starting balance.
+fn fail_account_creation(contract: &mut Contract, ticket_pk: &PublicKey) {
+    set_caller(&admin_id());
+    contract.create_nft_drop(
+        "image".to_string(),
+        "NFT".to_string(),
+        contract_key(),
+        None,
+        empty_metadata(),
+        None,
+    );
+    add_ticket(contract, ticket_pk);
+    create_scanned_account(contract, ticket_pk);
+    contract.internal_deposit_ft_mint(&account_id(), NearToken::from_yoctonear(50), None, false);
+    contract.internal_nft_mint(0, account_id());
+
+    set_account_creation_result(PromiseResult::Failed);
+    assert!(!contract.resolve_account_creation(
+        account_id(),
+        ticket_pk.clone(),
+        NearToken::from_yoctonear(STARTING_TOKENS),
+        false,
+    ));
+}
+
+#[test]
+fn failed_account_creation_only_burns_starting_tokens() {
+    let mut contract = setup_contract();
+    let (_, ticket_pk) = keypair(1);
+    fail_account_creation(&mut contract, &ticket_pk);
+
+    // The starting tokens are burned while the rest is held on the ticket
+    assert_eq!(contract.ft_total_supply().as_yoctonear(), 50);
+    assert!(contract.account_details_by_id.get(&account_id()).is_none());
+    let ticket = contract.attendee_ticket_by_pk.get(&ticket_pk).unwrap();
+    assert!(ticket.account_id.is_none());
+    assert_eq!(ticket.held_tokens.as_yoctonear(), 50);
+    assert_eq!(ticket.held_nft_ids, vec!["0:1".to_string()]);
+    assert_eq!(nft_owner(&contract, "0:1"), contract_id());
+}
+
+#[test]
+fn held_assets_go_to_the_next_account() {
+    let mut contract = setup_contract();
+    let (_, ticket_pk) = keypair(1);
+    fail_account_creation(&mut contract, &ticket_pk);
+
+    set_ticket_signer(&ticket_pk);
+    contract.create_account(account_id());
+    assert_eq!(contract.ft_balance_of(account_id()).as_yoctonear(), 150);
+    assert_eq!(contract.ft_total_supply().as_yoctonear(), 150);
+    assert_eq!(nft_owner(&contract, "0:1"), account_id());
+    let ticket = contract.attendee_ticket_by_pk.get(&ticket_pk).unwrap();
+    assert!(ticket.held_tokens.is_zero());
+    assert!(ticket.held_nft_ids.is_empty());
+}
+
+#[test]
+fn account_creation_succeeds_when_wallet_init_returns_a_value() {
+    let mut contract = setup_contract();
+    let (_, ticket_pk) = keypair(1);
+    add_ticket(&mut contract, &ticket_pk);
+    create_scanned_account(&mut contract, &ticket_pk);
+
+    // The wallet's init method returns something other than `()`
+    set_account_creation_result(PromiseResult::Successful(b"\"initialized\"".to_vec()));
+    assert!(contract.resolve_account_creation(
+        account_id(),
+        ticket_pk.clone(),
+        NearToken::from_yoctonear(STARTING_TOKENS),
+        false,
+    ));
+    assert_eq!(
+        contract.ft_balance_of(account_id()).as_yoctonear(),
+        STARTING_TOKENS
+    );
+    let ticket = contract.attendee_ticket_by_pk.get(&ticket_pk).unwrap();
+    assert_eq!(ticket.account_id, Some(account_id()));
+}
+
+#[test]
+fn accounts_get_the_default_wallet() {
+    let mut contract = setup_contract();
+    let (_, ticket_pk) = keypair(1);
+    upload_wallet(&mut contract, "v1", true);
+    add_ticket(&mut contract, &ticket_pk);
+
+    create_scanned_account(&mut contract, &ticket_pk);
+    assert_eq!(deployed_wallet_code(), Some(b"v1".to_vec()));
+}
+
+#[test]
+fn ticket_types_can_pick_their_wallet() {
+    let mut contract = setup_contract();
+    let (_, ticket_pk) = keypair(1);
+    upload_wallet(&mut contract, "v1", true);
+    upload_wallet(&mut contract, "v2", false);
+    add_ticket(&mut contract, &ticket_pk);
+    let ticket_data = contract.ticket_data_by_id.get("ga").unwrap();
+    let ticket_data = TicketType {
+        wallet_version: Some("v2".to_string()),
+        ..ticket_data.clone()
+    };
+    contract.update_ticket_data("ga".to_string(), ticket_data);
+
+    create_scanned_account(&mut contract, &ticket_pk);
+    assert_eq!(deployed_wallet_code(), Some(b"v2".to_vec()));
+}
+
+#[test]
+fn accounts_are_created_without_a_wallet_by_default() {
+    let mut contract = setup_contract();
+    let (_, ticket_pk) = keypair(1);
+    upload_wallet(&mut contract, "v1", false);
+    add_ticket(&mut contract, &ticket_pk);
+
+    create_scanned_account(&mut contract, &ticket_pk);
+    assert_eq!(deployed_wallet_code(), None);
+}
+
+#[test]
+#[should_panic(expected = "Cannot remove the default wallet version")]
+fn default_wallet_cannot_be_removed() {
+    let mut contract = setup_contract();
+    upload_wallet(&mut contract, "v1", true);
+    contract.remove_wallet_code("v1".to_string());
+}
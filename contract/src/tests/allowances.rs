@@ -0,0 +1,83 @@
+use super::*;
+
+fn sponsor_id() -> AccountId {
+    "sponsor.testnet".parse().unwrap()
+}
+
+fn worker_id() -> AccountId {
+    "worker.testnet".parse().unwrap()
+}
+
+fn alice_id() -> AccountId {
+    "alice.testnet".parse().unwrap()
+}
+
+/// Sets up the context for a call made at the given time with the 1 yoctoNEAR deposit token
+/// methods need.
+fn set_yocto_caller_at(predecessor: &AccountId, block_timestamp: u64) {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(contract_id())
+        .predecessor_account_id(predecessor.clone())
+        .signer_account_id(predecessor.clone())
+        .attached_deposit(NearToken::from_yoctonear(1))
+        .block_timestamp(block_timestamp)
+        .build());
+}
+
+/// Sets up a funded sponsor that lets the worker spend some of its tokens.
+fn setup_allowance(amount: u128, expires_at: Option<u64>) -> Contract {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 100);
+    add_account(&mut contract, &worker_id());
+    add_account(&mut contract, &alice_id());
+
+    set_yocto_caller_at(&sponsor_id(), 0);
+    contract.ft_approve(worker_id(), U128(amount), expires_at.map(U64));
+    contract
+}
+
+#[test]
+fn transfers_use_up_the_allowance() {
+    let mut contract = setup_allowance(30, None);
+
+    set_yocto_caller_at(&worker_id(), 0);
+    let amount_left = contract.ft_transfer_from(sponsor_id(), alice_id(), U128(20), None);
+    assert_eq!(amount_left.0, 10);
+    assert_eq!(contract.ft_balance_of(sponsor_id()).as_yoctonear(), 80);
+    assert_eq!(contract.ft_balance_of(alice_id()).as_yoctonear(), 20);
+    assert_eq!(contract.ft_allowance(sponsor_id(), worker_id()).0, 10);
+
+    // The allowance is removed once it's been spent
+    contract.ft_transfer_from(sponsor_id(), alice_id(), U128(10), None);
+    assert!(contract.get_ft_allowances(sponsor_id()).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Amount exceeds allowance")]
+fn transfers_cannot_exceed_the_allowance() {
+    let mut contract = setup_allowance(30, None);
+
+    set_yocto_caller_at(&worker_id(), 0);
+    contract.ft_transfer_from(sponsor_id(), alice_id(), U128(31), None);
+}
+
+#[test]
+#[should_panic(expected = "Allowance has expired")]
+fn expired_allowances_cannot_be_used() {
+    let mut contract = setup_allowance(30, Some(1000));
+    assert_eq!(contract.ft_allowance(sponsor_id(), worker_id()).0, 30);
+
+    set_yocto_caller_at(&worker_id(), 1001);
+    assert_eq!(contract.ft_allowance(sponsor_id(), worker_id()).0, 0);
+    contract.ft_transfer_from(sponsor_id(), alice_id(), U128(10), None);
+}
+
+#[test]
+#[should_panic(expected = "No allowance found for spender")]
+fn revoked_allowances_cannot_be_used() {
+    let mut contract = setup_allowance(30, None);
+    contract.ft_revoke(worker_id());
+
+    set_yocto_caller_at(&worker_id(), 0);
+    contract.ft_transfer_from(sponsor_id(), alice_id(), U128(10), None);
+}
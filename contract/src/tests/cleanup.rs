@@ -0,0 +1,153 @@
+use super::*;
+
+fn sponsor_id() -> AccountId {
+    "sponsor.testnet".parse().unwrap()
+}
+
+#[test]
+fn clear_storage_returns_drop_budgets_to_creators() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 100);
+    let drop_id = create_token_drop(&mut contract, &sponsor_id(), 10, 3);
+    assert_eq!(
+        contract.drop_by_id.get(&drop_id).unwrap().get_budget(),
+        Some(NearToken::from_yoctonear(30))
+    );
+    assert_eq!(contract.ft_balance_of(sponsor_id()).as_yoctonear(), 70);
+
+    let mut contract = commit(contract);
+    freeze(&mut contract);
+    contract.clear_storage(None, contract_id(), None);
+    assert!(contract.drop_by_id.is_empty());
+    // The budget went back into circulation rather than being burned
+    assert_eq!(contract.ft_total_supply.as_yoctonear(), 100);
+}
+
+#[test]
+fn clear_storage_burns_budgets_of_removed_creators() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 100);
+    create_token_drop(&mut contract, &sponsor_id(), 10, 3);
+    contract.account_details_by_id.remove(&sponsor_id());
+
+    let mut contract = commit(contract);
+    freeze(&mut contract);
+    // Only clear the drops so the sponsor's own tokens are left alone
+    contract.clear_storage(Some(0), contract_id(), None);
+    assert!(contract.drop_by_id.is_empty());
+    assert_eq!(contract.ft_total_supply.as_yoctonear(), 70);
+}
+
+#[test]
+fn clear_storage_burns_budgets_of_offboarded_creators() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 100);
+    create_token_drop(&mut contract, &sponsor_id(), 10, 3);
+    contract
+        .account_details_by_id
+        .get_mut(&sponsor_id())
+        .unwrap()
+        .offboarded_to = Some(admin_id());
+
+    let mut contract = commit(contract);
+    freeze(&mut contract);
+    // Only clear the drops so the sponsor's own tokens are left alone
+    contract.clear_storage(Some(0), contract_id(), None);
+    assert!(contract.drop_by_id.is_empty());
+    assert_eq!(contract.ft_total_supply.as_yoctonear(), 70);
+    assert_eq!(contract.ft_balance_of(sponsor_id()).as_yoctonear(), 70);
+}
+
+#[test]
+fn clear_storage_skips_accounts_that_have_not_offboarded() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 100);
+    let offboarded_id: AccountId = "bob.testnet".parse().unwrap();
+    add_account(&mut contract, &offboarded_id);
+    contract
+        .account_details_by_id
+        .get_mut(&offboarded_id)
+        .unwrap()
+        .offboarded_to = Some(admin_id());
+
+    let mut contract = commit(contract);
+    freeze(&mut contract);
+    // The admin and sponsor are left until the clear is forced
+    assert_eq!(contract.clear_storage(None, contract_id(), None), 2);
+    assert!(contract.account_details_by_id.get(&offboarded_id).is_none());
+    assert_eq!(contract.ft_balance_of(sponsor_id()).as_yoctonear(), 100);
+
+    assert_eq!(contract.clear_storage(None, contract_id(), Some(true)), 0);
+    assert_eq!(contract.ft_total_supply.as_yoctonear(), 0);
+}
+
+#[test]
+fn clear_storage_only_clears_one_batch() {
+    let mut contract = setup_contract();
+    for i in 0..5 {
+        add_account(
+            &mut contract,
+            &format!("user{}.testnet", i).parse().unwrap(),
+        );
+    }
+
+    let mut contract = commit(contract);
+    freeze(&mut contract);
+    assert_eq!(
+        contract.clear_storage(Some(2), contract_id(), Some(true)),
+        4
+    );
+    assert_eq!(contract.account_details_by_id.len(), 4);
+    assert_eq!(
+        contract.clear_storage(Some(10), contract_id(), Some(true)),
+        0
+    );
+}
+
+#[test]
+fn offboarding_burns_tokens_so_the_account_can_be_cleared() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 100);
+
+    freeze(&mut contract);
+    set_caller(&sponsor_id());
+    contract.offboard(admin_id());
+    assert_eq!(contract.ft_balance_of(sponsor_id()).as_yoctonear(), 0);
+    assert_eq!(contract.ft_total_supply.as_yoctonear(), 0);
+    assert_eq!(
+        contract
+            .account_details_by_id
+            .get(&sponsor_id())
+            .unwrap()
+            .offboarded_to,
+        Some(admin_id())
+    );
+
+    let mut contract = commit(contract);
+    set_caller(&contract_id());
+    // Only the admin is left since it never offboarded
+    assert_eq!(contract.clear_storage(None, contract_id(), None), 1);
+    assert!(contract.account_details_by_id.get(&sponsor_id()).is_none());
+}
+
+#[test]
+#[should_panic(expected = "Account has already offboarded")]
+fn accounts_cannot_offboard_twice() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 100);
+
+    freeze(&mut contract);
+    set_caller(&sponsor_id());
+    contract.offboard(admin_id());
+    contract.offboard(admin_id());
+}
+
+#[test]
+#[should_panic(expected = "Offboarding is only available once the conference is over")]
+fn accounts_cannot_offboard_before_the_freeze() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 100);
+
+    set_caller(&sponsor_id());
+    contract.offboard(admin_id());
+}
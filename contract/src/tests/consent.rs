@@ -0,0 +1,63 @@
+use crate::consent::grants::MAX_ENCRYPTED_METADATA_LEN;
+
+use super::*;
+
+fn sponsor_id() -> AccountId {
+    "sponsor.testnet".parse().unwrap()
+}
+
+fn attendee_id() -> AccountId {
+    "alice.testnet".parse().unwrap()
+}
+
+#[test]
+fn attendee_can_grant_metadata_access() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 0);
+    add_account(&mut contract, &attendee_id());
+
+    set_caller(&attendee_id());
+    contract.grant_metadata_access(sponsor_id(), "a".repeat(MAX_ENCRYPTED_METADATA_LEN));
+    assert_eq!(
+        contract
+            .account_details_by_id
+            .get(&sponsor_id())
+            .unwrap()
+            .metadata_grants_received
+            .len(),
+        1
+    );
+}
+
+#[test]
+#[should_panic(expected = "Encrypted metadata is too long")]
+fn metadata_grants_are_capped() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 0);
+    add_account(&mut contract, &attendee_id());
+
+    set_caller(&attendee_id());
+    contract.grant_metadata_access(sponsor_id(), "a".repeat(MAX_ENCRYPTED_METADATA_LEN + 1));
+}
+
+#[test]
+fn attendee_can_revoke_metadata_access() {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 0);
+    add_account(&mut contract, &attendee_id());
+
+    set_caller(&attendee_id());
+    contract.grant_metadata_access(sponsor_id(), "metadata".to_string());
+    assert_eq!(
+        contract.get_metadata_grants_by_attendee(attendee_id()),
+        vec![sponsor_id()]
+    );
+
+    contract.revoke_metadata_access(sponsor_id());
+    assert!(contract
+        .get_metadata_grants_by_attendee(attendee_id())
+        .is_empty());
+    assert!(contract
+        .get_metadata_grant(attendee_id(), sponsor_id())
+        .is_none());
+}
@@ -0,0 +1,395 @@
+use super::*;
+
+fn create_nft_drop(contract: &mut Contract, config: DropConfig) -> DropId {
+    set_caller(&admin_id());
+    contract.create_nft_drop(
+        "image".to_string(),
+        "NFT".to_string(),
+        contract_key(),
+        None,
+        empty_metadata(),
+        Some(config),
+    )
+}
+
+fn drop_config(contract: &Contract, drop_id: &DropId) -> DropConfig {
+    contract
+        .drop_by_id
+        .get(drop_id)
+        .unwrap()
+        .get_config()
+        .clone()
+}
+
+fn config_update(json: &str) -> DropConfigUpdate {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn update_drop_config_only_changes_passed_fields() {
+    let mut contract = setup_contract();
+    let drop_id = create_nft_drop(
+        &mut contract,
+        DropConfig {
+            max_claims: Some(5),
+            starts_at: Some(10),
+            ends_at: Some(100),
+            paused: true,
+            ordered_scavenger_hunt: true,
+            legacy_claim_signatures: false,
+        },
+    );
+
+    contract.update_drop_config(drop_id.clone(), config_update(r#"{"max_claims": 10}"#));
+    let config = drop_config(&contract, &drop_id);
+    assert_eq!(config.max_claims, Some(10));
+    assert_eq!(config.starts_at, Some(10));
+    assert_eq!(config.ends_at, Some(100));
+    assert!(config.paused);
+    assert!(config.ordered_scavenger_hunt);
+
+    contract.update_drop_config(
+        drop_id.clone(),
+        config_update(r#"{"ends_at": null, "paused": false}"#),
+    );
+    let config = drop_config(&contract, &drop_id);
+    assert_eq!(config.max_claims, Some(10));
+    assert_eq!(config.starts_at, Some(10));
+    assert_eq!(config.ends_at, None);
+    assert!(!config.paused);
+    assert!(config.ordered_scavenger_hunt);
+}
+
+#[test]
+#[should_panic(expected = "Drop must start before it ends")]
+fn update_drop_config_validates_merged_window() {
+    let mut contract = setup_contract();
+    let drop_id = create_nft_drop(
+        &mut contract,
+        DropConfig {
+            starts_at: Some(10),
+            ends_at: Some(100),
+            ..Default::default()
+        },
+    );
+
+    contract.update_drop_config(drop_id, config_update(r#"{"starts_at": 200}"#));
+}
+
+#[test]
+#[should_panic(expected = "Nothing to update")]
+fn update_drop_config_requires_a_change() {
+    let mut contract = setup_contract();
+    let drop_id = create_nft_drop(&mut contract, DropConfig::default());
+
+    contract.update_drop_config(drop_id, config_update("{}"));
+}
+
+#[test]
+fn update_drop_keeps_nft_series_in_sync() {
+    let mut contract = setup_contract();
+    let drop_id = create_nft_drop(&mut contract, DropConfig::default());
+
+    let metadata = TokenMetadata {
+        title: Some("Updated".to_string()),
+        ..empty_metadata()
+    };
+    contract.update_drop(
+        drop_id.clone(),
+        Some("Renamed".to_string()),
+        None,
+        None,
+        Some(metadata),
+        None,
+    );
+
+    let drop_data = contract.drop_by_id.get(&drop_id).unwrap();
+    assert_eq!(drop_data.get_name(), "Renamed");
+    let DropData::Nft(data) = drop_data else {
+        panic!("Expected an NFT drop");
+    };
+    let series = contract.series_by_id.get(&data.nft_series_id).unwrap();
+    assert_eq!(series.metadata.title.as_deref(), Some("Updated"));
+}
+
+#[test]
+#[should_panic(expected = "Token amount can only be updated for token drops")]
+fn update_drop_rejects_token_amount_for_nft_drops() {
+    let mut contract = setup_contract();
+    let drop_id = create_nft_drop(&mut contract, DropConfig::default());
+
+    contract.update_drop(drop_id, None, None, Some(U128(5)), None, None);
+}
+
+#[test]
+#[should_panic(expected = "Nothing to update")]
+fn update_drop_requires_a_change() {
+    let mut contract = setup_contract();
+    let drop_id = create_nft_drop(&mut contract, DropConfig::default());
+
+    contract.update_drop(drop_id, None, None, None, None, None);
+}
+
+fn nft_drop_args() -> DropCreationArgs {
+    DropCreationArgs {
+        image: "image".to_string(),
+        name: "NFT".to_string(),
+        key: contract_key(),
+        scavenger_hunt: None,
+        scavenger_milestones: None,
+        config: None,
+        reward: DropCreationReward::Nft {
+            nft_metadata: empty_metadata(),
+        },
+    }
+}
+
+#[test]
+fn create_drops_counts_one_transaction() {
+    let mut contract = setup_contract();
+    set_caller(&admin_id());
+    let total_transactions = contract.total_transactions;
+
+    let drop_ids = contract.create_drops(vec![nft_drop_args(); 3]);
+    assert_eq!(drop_ids.len(), 3);
+    assert_eq!(contract.total_transactions, total_transactions + 1);
+}
+
+#[test]
+#[should_panic(expected = "Maximum number of drops exceeded")]
+fn create_drops_enforces_batch_limit() {
+    let mut contract = setup_contract();
+    set_caller(&admin_id());
+    contract.create_drops(vec![nft_drop_args(); MAX_DROPS_PER_BATCH + 1]);
+}
+
+#[test]
+fn update_drop_config_rebalances_token_budget() {
+    let mut contract = setup_contract();
+    let sponsor_id: AccountId = "sponsor.testnet".parse().unwrap();
+    add_sponsor(&mut contract, &sponsor_id, 100);
+    let drop_id = create_token_drop(&mut contract, &sponsor_id, 10, 3);
+    assert_eq!(
+        contract.ft_balance_of(sponsor_id.clone()).as_yoctonear(),
+        70
+    );
+
+    contract.update_drop_config(drop_id.clone(), config_update(r#"{"max_claims": 5}"#));
+    assert_eq!(
+        contract.ft_balance_of(sponsor_id.clone()).as_yoctonear(),
+        50
+    );
+
+    contract.update_drop_config(drop_id, config_update(r#"{"max_claims": 1}"#));
+    assert_eq!(contract.ft_balance_of(sponsor_id).as_yoctonear(), 90);
+}
+
+#[test]
+#[should_panic(expected = "The creator does not have enough tokens to fund this drop")]
+fn token_drop_budget_must_be_funded() {
+    let mut contract = setup_contract();
+    let sponsor_id: AccountId = "sponsor.testnet".parse().unwrap();
+    add_sponsor(&mut contract, &sponsor_id, 20);
+    create_token_drop(&mut contract, &sponsor_id, 10, 3);
+}
+
+fn scavenger_piece(id: u16, key: &str) -> ScavengerHuntData {
+    ScavengerHuntData {
+        key: format!("ed25519:{}", key).parse().unwrap(),
+        id,
+        description: format!("Piece {}", id),
+    }
+}
+
+#[test]
+fn ordered_hunts_point_to_the_lowest_piece_left() {
+    let mut contract = setup_contract();
+    set_caller(&admin_id());
+    let pieces = vec![
+        scavenger_piece(2, "DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e"),
+        scavenger_piece(1, "H8tBNhpsXj8TC6LgmJXpTZsh5Vz2Zh9r6NYQzZYZ8Kzm"),
+    ];
+    let drop_id = contract.create_nft_drop(
+        "image".to_string(),
+        "Hunt".to_string(),
+        contract_key(),
+        Some(pieces.clone()),
+        empty_metadata(),
+        Some(DropConfig {
+            ordered_scavenger_hunt: true,
+            ..Default::default()
+        }),
+    );
+    let drop_data = contract.drop_by_id.get(&drop_id).unwrap();
+
+    let next_piece = drop_data.get_next_scavenger_piece(&None).unwrap();
+    assert_eq!(next_piece.id, 1);
+    let next_piece = drop_data
+        .get_next_scavenger_piece(&Some(vec![pieces[1].key.clone()]))
+        .unwrap();
+    assert_eq!(next_piece.id, 2);
+    assert!(drop_data
+        .get_next_scavenger_piece(&Some(vec![pieces[0].key.clone(), pieces[1].key.clone()]))
+        .is_none());
+}
+
+#[test]
+fn unordered_hunts_have_no_next_piece() {
+    let mut contract = setup_contract();
+    set_caller(&admin_id());
+    let drop_id = contract.create_nft_drop(
+        "image".to_string(),
+        "Hunt".to_string(),
+        contract_key(),
+        Some(vec![scavenger_piece(
+            1,
+            "H8tBNhpsXj8TC6LgmJXpTZsh5Vz2Zh9r6NYQzZYZ8Kzm",
+        )]),
+        empty_metadata(),
+        None,
+    );
+
+    let drop_data = contract.drop_by_id.get(&drop_id).unwrap();
+    assert!(drop_data.get_next_scavenger_piece(&None).is_none());
+}
+
+fn claimer(name: &str) -> AccountId {
+    format!("{}.testnet", name).parse().unwrap()
+}
+
+/// Creates a sponsor token drop with a three piece hunt and a milestone after the first piece.
+fn create_hunt_with_milestone(
+    contract: &mut Contract,
+    sponsor_id: &AccountId,
+    max_claims: u64,
+) -> (DropId, Vec<(ed25519_dalek::SigningKey, PublicKey)>) {
+    let pieces: Vec<_> = (1..=3).map(keypair).collect();
+    set_caller(sponsor_id);
+    let drop_ids = contract.create_drops(vec![DropCreationArgs {
+        image: "image".to_string(),
+        name: "Hunt".to_string(),
+        key: keypair(0).1,
+        scavenger_hunt: Some(
+            pieces
+                .iter()
+                .enumerate()
+                .map(|(id, (_, key))| ScavengerHuntData {
+                    key: key.clone(),
+                    id: id as u16,
+                    description: format!("Piece {}", id),
+                })
+                .collect(),
+        ),
+        scavenger_milestones: Some(vec![ScavengerMilestone {
+            pieces_found: 1,
+            token_amount: U128(10),
+            num_paid: 0,
+        }]),
+        config: Some(DropConfig {
+            max_claims: Some(max_claims),
+            ..Default::default()
+        }),
+        reward: DropCreationReward::Token {
+            token_amount: U128(100),
+        },
+    }]);
+    (drop_ids[0].clone(), pieces)
+}
+
+fn find_piece(
+    contract: &mut Contract,
+    claimer_id: &AccountId,
+    drop_id: &DropId,
+    piece: &(ed25519_dalek::SigningKey, PublicKey),
+) {
+    claim(
+        contract,
+        claimer_id,
+        drop_id,
+        &piece.0,
+        Some(piece.1.clone()),
+    );
+}
+
+#[test]
+fn milestones_have_their_own_budget() {
+    let mut contract = setup_contract();
+    let sponsor_id = claimer("sponsor");
+    add_sponsor(&mut contract, &sponsor_id, 1000);
+    let (drop_id, pieces) = create_hunt_with_milestone(&mut contract, &sponsor_id, 2);
+
+    // The drop reward and the milestone are each funded for every claim
+    let budget = |contract: &Contract| {
+        contract
+            .drop_by_id
+            .get(&drop_id)
+            .unwrap()
+            .get_budget()
+            .unwrap()
+            .as_yoctonear()
+    };
+    assert_eq!(budget(&contract), 2 * 100 + 2 * 10);
+
+    // Three claimers reach the milestone but only two can be paid
+    for name in ["alice", "bob", "carol"] {
+        add_account(&mut contract, &claimer(name));
+        find_piece(&mut contract, &claimer(name), &drop_id, &pieces[0]);
+    }
+    assert_eq!(contract.ft_balance_of(claimer("alice")).as_yoctonear(), 10);
+    assert_eq!(contract.ft_balance_of(claimer("bob")).as_yoctonear(), 10);
+    assert_eq!(contract.ft_balance_of(claimer("carol")).as_yoctonear(), 0);
+    assert_eq!(budget(&contract), 2 * 100);
+
+    // Carol's piece is still recorded and milestone payouts didn't use up the drop's claims
+    let drop = contract.drop_by_id.get(&drop_id).unwrap();
+    assert_eq!(drop.get_num_rewarded(), 0);
+    assert_eq!(drop.get_scavenger_milestone(1).unwrap().num_paid, 2);
+    assert_eq!(
+        contract
+            .account_details_by_id
+            .get(&claimer("carol"))
+            .unwrap()
+            .drops_claimed
+            .get(&drop_id)
+            .cloned()
+            .flatten()
+            .unwrap(),
+        vec![pieces[0].1.clone()]
+    );
+
+    // Carol and Bob can still complete the hunt and get the drop's reward
+    for name in ["carol", "bob"] {
+        find_piece(&mut contract, &claimer(name), &drop_id, &pieces[1]);
+        find_piece(&mut contract, &claimer(name), &drop_id, &pieces[2]);
+    }
+    assert_eq!(contract.ft_balance_of(claimer("carol")).as_yoctonear(), 100);
+    assert_eq!(contract.ft_balance_of(claimer("bob")).as_yoctonear(), 110);
+    assert_eq!(budget(&contract), 0);
+}
+
+#[test]
+fn raising_max_claims_funds_milestones() {
+    let mut contract = setup_contract();
+    let sponsor_id = claimer("sponsor");
+    add_sponsor(&mut contract, &sponsor_id, 1000);
+    let (drop_id, pieces) = create_hunt_with_milestone(&mut contract, &sponsor_id, 1);
+    add_account(&mut contract, &claimer("alice"));
+    find_piece(&mut contract, &claimer("alice"), &drop_id, &pieces[0]);
+
+    set_caller(&sponsor_id);
+    contract.update_drop_config(
+        drop_id.clone(),
+        DropConfigUpdate {
+            max_claims: Some(Some(3)),
+            ..Default::default()
+        },
+    );
+
+    // The drop reward has three claims left and the milestone two payouts left
+    let drop = contract.drop_by_id.get(&drop_id).unwrap();
+    assert_eq!(drop.get_budget().unwrap().as_yoctonear(), 3 * 100 + 2 * 10);
+    assert_eq!(
+        contract.ft_balance_of(sponsor_id).as_yoctonear(),
+        1000 - 3 * 100 - 3 * 10
+    );
+}
@@ -0,0 +1,109 @@
+use super::*;
+
+fn alice_id() -> AccountId {
+    "alice.testnet".parse().unwrap()
+}
+
+fn old_state() -> OldState {
+    let mut account_details_by_id = IterableMap::new(StorageKeys::AccountDetailsByIdNew);
+    let account_details = AccountDetails::new(&alice_id());
+    account_details_by_id.insert(
+        alice_id(),
+        OldAccountDetails {
+            account_status: Some(AccountStatus::Sponsor),
+            ft_balance: NearToken::from_yoctonear(40),
+            tokens_collected: NearToken::from_yoctonear(0),
+            drops_created: account_details.drops_created,
+            drop_nonce: 2,
+            claim_nonce: 3,
+            drops_claimed: account_details.drops_claimed,
+            multichain_mints: account_details.multichain_mints,
+            metadata_grants_received: account_details.metadata_grants_received,
+            metadata_grants_given: account_details.metadata_grants_given,
+            offboarded_to: Some(admin_id()),
+        },
+    );
+    account_details_by_id.flush();
+    let mut vendor_info_by_id = IterableMap::new(StorageKeys::VendorInfoById);
+    vendor_info_by_id.insert(alice_id(), VendorInformation::new(&alice_id()));
+    vendor_info_by_id.flush();
+
+    OldState {
+        account_details_by_id,
+        is_contract_frozen: false,
+        contract_key: contract_key(),
+        ft_total_supply: NearToken::from_yoctonear(40),
+        ft_metadata: FungibleTokenMetadata {
+            spec: "ft-1.0.0".to_string(),
+            name: "Token".to_string(),
+            symbol: "TKN".to_string(),
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            decimals: 24,
+        },
+        nft_tokens_by_id: IterableMap::new(StorageKeys::TokensById),
+        nft_tokens_per_owner: LookupMap::new(StorageKeys::TokensForOwner),
+        series_by_id: IterableMap::new(StorageKeys::SeriesById),
+        nft_metadata: NFTContractMetadata {
+            spec: "nft-1.0.0".to_string(),
+            name: "NFT".to_string(),
+            symbol: "NFT".to_string(),
+            icon: None,
+            base_uri: None,
+            reference: None,
+            reference_hash: None,
+        },
+        drop_by_id: IterableMap::new(StorageKeys::DropById),
+        mpc_contract: "signer.testnet".parse().unwrap(),
+        mpc_sign_deposit: NearToken::from_millinear(5),
+        ticket_data_by_id: IterableMap::new(StorageKeys::TicketDataById),
+        ticket_stats_by_id: IterableMap::new(StorageKeys::TicketStatsById),
+        reserved_account_names: IterableSet::new(StorageKeys::ReservedAccountNames),
+        wallet_contracts: IterableMap::new(StorageKeys::WalletContracts),
+        wallet_code_by_version: LookupMap::new(StorageKeys::WalletCodeByVersion),
+        default_wallet_version: Some("v1".to_string()),
+        vendor_info_by_id,
+        exchange_policy: ExchangePolicy::Burn,
+        token_leaderboard: vec![],
+        poap_leaderboard: vec![],
+        recent_transactions: vec![],
+        total_transactions: 0,
+        total_tokens_transferred: NearToken::from_yoctonear(0),
+        attendee_ticket_by_pk: IterableMap::new(StorageKeys::AttendeeTicketInformation),
+        check_ins_by_location: IterableMap::new(StorageKeys::CheckInsByLocation),
+        attendance_by_session: IterableMap::new(StorageKeys::AttendanceBySession),
+        allow_self_check_in: true,
+        agenda: "[{}]".to_string(),
+        alerts: "[{}]".to_string(),
+        alerts_timestamp: 0,
+        agenda_timestamp: 0,
+    }
+}
+
+#[test]
+fn migrate_rewrites_changed_layouts() {
+    set_caller(&contract_id());
+    env::state_write(&old_state());
+    // Read the migrated state back from storage to make sure nothing old was left behind
+    let contract = commit(Contract::migrate());
+
+    let account_details = contract.account_details_by_id.get(&alice_id()).unwrap();
+    assert_eq!(account_details.ft_balance.as_yoctonear(), 40);
+    assert!(matches!(
+        account_details.account_status,
+        Some(AccountStatus::Sponsor)
+    ));
+    assert_eq!(account_details.drop_nonce, 2);
+    assert_eq!(account_details.claim_nonce, 3);
+    assert_eq!(account_details.offboarded_to, Some(admin_id()));
+    assert!(matches!(contract.exchange_policy, ExchangePolicy::Burn));
+    assert!(account_details.ft_allowances.is_empty());
+    assert_eq!(contract.vendor_info_by_id.len(), 1);
+    assert_eq!(
+        contract.get_default_wallet_version(),
+        Some("v1".to_string())
+    );
+    assert_eq!(contract.get_mpc_contract().as_str(), "signer.testnet");
+    assert!(contract.is_self_check_in_allowed());
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use ed25519_dalek::{Signer, SigningKey};
+use near_sdk::json_types::{Base64VecU8, U64};
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{testing_env, CurveType};
+
+use crate::*;
+
+mod accounts;
+mod allowances;
+mod cleanup;
+mod consent;
+mod drops;
+mod migration;
+mod multichain;
+mod signatures;
+mod standards;
+mod supply;
+mod tickets;
+mod vendors;
+
+pub(crate) fn contract_id() -> AccountId {
+    "keypom.testnet".parse().unwrap()
+}
+
+pub(crate) fn admin_id() -> AccountId {
+    "admin.testnet".parse().unwrap()
+}
+
+pub(crate) fn contract_key() -> PublicKey {
+    "ed25519:6E8sCci9badyRkXb3JoRpBj5p8C6Tw41ELDZoiihKEtp"
+        .parse()
+        .unwrap()
+}
+
+/// Sets up the blockchain context for a call made by the predecessor.
+pub(crate) fn set_caller(predecessor: &AccountId) {
+    set_caller_at(predecessor, 0);
+}
+
+/// Sets up the blockchain context for a call made by the predecessor at a block timestamp.
+pub(crate) fn set_caller_at(predecessor: &AccountId, block_timestamp: u64) {
+    let mut builder = VMContextBuilder::new();
+    builder
+        .current_account_id(contract_id())
+        .predecessor_account_id(predecessor.clone())
+        .signer_account_id(predecessor.clone())
+        .block_timestamp(block_timestamp);
+    // Calls from the contract account are signed with its full access key
+    if *predecessor == contract_id() {
+        builder.signer_account_pk(contract_key());
+    }
+    testing_env!(builder.build());
+}
+
+/// Sets up the context for a call signed by a ticket key on the contract account.
+pub(crate) fn set_ticket_signer(public_key: &PublicKey) {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(contract_id())
+        .predecessor_account_id(contract_id())
+        .signer_account_id(contract_id())
+        .signer_account_pk(public_key.clone())
+        .build());
+}
+
+/// Writes the contract and its collections to storage like at the end of a transaction.
+pub(crate) fn commit(contract: Contract) -> Contract {
+    env::state_write(&contract);
+    // Dropping the contract flushes its collections
+    drop(contract);
+    env::state_read().unwrap()
+}
+
+/// Freezes the contract as the contract account.
+pub(crate) fn freeze(contract: &mut Contract) {
+    set_caller(&contract_id());
+    contract.toggle_freeze(true);
+}
+
+/// Creates a contract with a single admin.
+pub(crate) fn setup_contract() -> Contract {
+    set_caller(&contract_id());
+    Contract::new(
+        HashMap::new(),
+        None,
+        None,
+        None,
+        vec![admin_id()],
+        contract_key(),
+    )
+}
+
+/// Registers a basic account on the contract.
+pub(crate) fn add_account(contract: &mut Contract, account_id: &AccountId) {
+    contract
+        .account_details_by_id
+        .insert(account_id.clone(), AccountDetails::new(account_id));
+}
+
+/// Registers a sponsor on the contract and mints them some tokens.
+pub(crate) fn add_sponsor(contract: &mut Contract, account_id: &AccountId, tokens: u128) {
+    let mut account_details = AccountDetails::new(account_id);
+    account_details.account_status = Some(AccountStatus::Sponsor);
+    contract
+        .account_details_by_id
+        .insert(account_id.clone(), account_details);
+    contract.internal_deposit_ft_mint(account_id, NearToken::from_yoctonear(tokens), None, false);
+}
+
+/// Creates a token drop as the sponsor, funding its budget from their balance.
+pub(crate) fn create_token_drop(
+    contract: &mut Contract,
+    sponsor_id: &AccountId,
+    token_amount: u128,
+    max_claims: u64,
+) -> DropId {
+    set_caller(sponsor_id);
+    contract.create_token_drop(
+        "image".to_string(),
+        "Tokens".to_string(),
+        None,
+        contract_key(),
+        U128(token_amount),
+        Some(DropConfig {
+            max_claims: Some(max_claims),
+            ..Default::default()
+        }),
+    )
+}
+
+pub(crate) fn empty_metadata() -> TokenMetadata {
+    TokenMetadata {
+        title: None,
+        description: None,
+        media: None,
+        media_hash: None,
+        copies: None,
+        issued_at: None,
+        expires_at: None,
+        starts_at: None,
+        updated_at: None,
+        extra: None,
+        reference: None,
+        reference_hash: None,
+    }
+}
+
+/// Creates a deterministic ed25519 key pair, returning the signing key and its NEAR public key.
+pub(crate) fn keypair(seed: u8) -> (SigningKey, PublicKey) {
+    let signing_key = SigningKey::from_bytes(&[seed; 32]);
+    let public_key = PublicKey::from_parts(
+        CurveType::ED25519,
+        signing_key.verifying_key().to_bytes().to_vec(),
+    )
+    .unwrap();
+    (signing_key, public_key)
+}
+
+/// Claims a drop as the claimer, signing the claim with the drop key or, if a scavenger piece is
+/// passed in, with the piece's key.
+pub(crate) fn claim(
+    contract: &mut Contract,
+    claimer_id: &AccountId,
+    drop_id: &DropId,
+    signing_key: &SigningKey,
+    scavenger_id: Option<PublicKey>,
+) -> ExtClaimedDrop {
+    set_caller(claimer_id);
+    let expires_at = 1_000_000;
+    let message = format!(
+        "{},{},{},{},{},{}",
+        CLAIM_MESSAGE_VERSION,
+        drop_id,
+        claimer_id,
+        bs58::encode(signing_key.verifying_key().to_bytes()).into_string(),
+        contract.get_claim_nonce(claimer_id.clone()),
+        expires_at
+    );
+    let signature = signing_key.sign(message.as_bytes()).to_bytes().to_vec();
+    contract.claim_drop(
+        drop_id.clone(),
+        scavenger_id,
+        Base64VecU8(signature),
+        Some(U64(expires_at)),
+    )
+}
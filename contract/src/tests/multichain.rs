@@ -0,0 +1,292 @@
+use near_sdk::mock::MockAction;
+use near_sdk::test_utils::get_created_receipts;
+use near_sdk::PromiseError;
+
+use super::*;
+
+const CHAIN_ID: u64 = 11155111;
+const NFT_CONTRACT: &str = "0x1234567890abcdef1234567890abcdef12345678";
+const SERIES_ID: SeriesId = 7;
+// EIP-712 signing hash of Mint(7, "alice.test.near") for the domain above, computed with
+// alloy-sol-types
+const EXPECTED_DIGEST: &str = "422b176c7d00788901de2c55a3f08507fe8af0577dccd8e1b69e99d960cba88d";
+
+fn receiver_id() -> AccountId {
+    "alice.test.near".parse().unwrap()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn create_multichain_drop(contract: &mut Contract, nft_contract: &str) -> DropId {
+    set_caller(&admin_id());
+    contract.create_multichain_drop(
+        "image".to_string(),
+        "POAP".to_string(),
+        contract_key(),
+        None,
+        MultichainMetadata {
+            chain_id: CHAIN_ID,
+            contract_id: nft_contract.to_string(),
+            series_id: SERIES_ID,
+        },
+        empty_metadata(),
+        None,
+    )
+}
+
+/// Mints for the receiver and returns the requests sent to the MPC signer.
+fn mint(contract: &mut Contract, drop_id: &DropId) -> Vec<serde_json::Value> {
+    let data = match contract.drop_by_id.get(drop_id).unwrap() {
+        DropData::Multichain(data) => data.clone(),
+        _ => panic!("Expected a multichain drop"),
+    };
+    contract.handle_multichain_mint(&data, drop_id, &receiver_id());
+    sign_requests()
+}
+
+fn sign_requests() -> Vec<serde_json::Value> {
+    get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id.as_str() == DEFAULT_MPC_CONTRACT)
+        .flat_map(|receipt| receipt.actions)
+        .filter_map(|action| match action {
+            MockAction::FunctionCallWeight {
+                method_name, args, ..
+            } if method_name == b"sign" => {
+                let args: serde_json::Value = serde_json::from_slice(&args).unwrap();
+                Some(args["request"].clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+pub(super) fn mock_signature() -> SignResult {
+    SignResult {
+        big_r: AffinePoint {
+            affine_point: "02E14D22E30DB6CE1CBA2B1A6B0B4F2BFCB1AE4CF37C1BFBCE2C2C7E3D9C0E6A4B"
+                .to_string(),
+        },
+        s: Scalar {
+            scalar: "3C3DB2A3C8D1A4F8E3B6C2B7F64E1F1D0A3F4C5B6A79880706050403020100FF".to_string(),
+        },
+        recovery_id: 1,
+    }
+}
+
+fn mint_status(contract: &Contract, drop_id: &DropId) -> MultichainMintStatus {
+    contract
+        .account_details_by_id
+        .get(&receiver_id())
+        .unwrap()
+        .multichain_mints
+        .get(drop_id)
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn mint_digest_matches_eip712() {
+    set_caller(&contract_id());
+    let digest = eip712_mint_digest(CHAIN_ID, NFT_CONTRACT, SERIES_ID, &receiver_id());
+    assert_eq!(to_hex(&digest), EXPECTED_DIGEST);
+
+    // The digest is bound to the chain and the contract
+    assert_ne!(
+        eip712_mint_digest(1, NFT_CONTRACT, SERIES_ID, &receiver_id()),
+        digest
+    );
+    assert_ne!(
+        eip712_mint_digest(
+            CHAIN_ID,
+            "0x0000000000000000000000000000000000000001",
+            SERIES_ID,
+            &receiver_id()
+        ),
+        digest
+    );
+}
+
+#[test]
+fn parse_evm_address_rejects_invalid_addresses() {
+    assert!(parse_evm_address(NFT_CONTRACT).is_some());
+    assert!(parse_evm_address("0xABCDEF7890abcdef1234567890abcdef12345678").is_some());
+    assert!(parse_evm_address("1234567890abcdef1234567890abcdef12345678").is_none());
+    assert!(parse_evm_address("0x1234567890abcdef1234567890abcdef123456").is_none());
+    assert!(parse_evm_address("0x1234567890abcdef1234567890abcdef1234567g").is_none());
+    assert!(parse_evm_address("nft.near").is_none());
+}
+
+#[test]
+#[should_panic(expected = "Contract ID must be a 0x prefixed EVM address")]
+fn multichain_drop_requires_evm_contract() {
+    let mut contract = setup_contract();
+    create_multichain_drop(&mut contract, "nft.near");
+}
+
+#[test]
+fn mock_signer_signs_mint_payload() {
+    let mut contract = setup_contract();
+    let drop_id = create_multichain_drop(&mut contract, NFT_CONTRACT);
+    add_account(&mut contract, &receiver_id());
+
+    let requests = mint(&mut contract, &drop_id);
+    assert_eq!(requests.len(), 1);
+    let payload: Vec<u8> = serde_json::from_value(requests[0]["payload"].clone()).unwrap();
+    assert_eq!(to_hex(&payload), EXPECTED_DIGEST);
+    assert_eq!(requests[0]["path"], CHAIN_ID.to_string());
+    assert_eq!(requests[0]["key_version"], MPC_KEY_VERSION);
+
+    let status = mint_status(&contract, &drop_id);
+    assert!(status.state == MultichainMintState::Pending);
+    assert_eq!(to_hex(&status.payload.payload), EXPECTED_DIGEST);
+
+    // The signer responds with a signature
+    set_caller(&contract_id());
+    let signature = contract
+        .resolve_multichain_mint(receiver_id(), drop_id.clone(), Ok(mock_signature()))
+        .unwrap();
+    assert_eq!(signature.recovery_id, 1);
+
+    let status = mint_status(&contract, &drop_id);
+    assert!(status.state == MultichainMintState::Signed);
+    assert_eq!(
+        status.signature.unwrap().s.scalar,
+        mock_signature().s.scalar
+    );
+    assert_eq!(status.attempts, 1);
+}
+
+#[test]
+fn admin_can_raise_sign_deposit() {
+    let mut contract = setup_contract();
+    let drop_id = create_multichain_drop(&mut contract, NFT_CONTRACT);
+    add_account(&mut contract, &receiver_id());
+
+    set_caller(&admin_id());
+    contract.set_mpc_sign_deposit(NearToken::from_millinear(50));
+    assert_eq!(
+        contract.get_mpc_sign_deposit(),
+        NearToken::from_millinear(50)
+    );
+
+    mint(&mut contract, &drop_id);
+    let deposits: Vec<NearToken> = get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id.as_str() == DEFAULT_MPC_CONTRACT)
+        .flat_map(|receipt| receipt.actions)
+        .filter_map(|action| match action {
+            MockAction::FunctionCallWeight {
+                attached_deposit, ..
+            } => Some(attached_deposit),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(deposits, vec![NearToken::from_millinear(50)]);
+}
+
+#[test]
+#[should_panic(expected = "No account status found")]
+fn only_admins_can_set_sign_deposit() {
+    let mut contract = setup_contract();
+    add_account(&mut contract, &receiver_id());
+    set_caller(&receiver_id());
+    contract.set_mpc_sign_deposit(NearToken::from_millinear(50));
+}
+
+#[test]
+fn failed_signature_can_be_retried() {
+    let mut contract = setup_contract();
+    let drop_id = create_multichain_drop(&mut contract, NFT_CONTRACT);
+    add_account(&mut contract, &receiver_id());
+    mint(&mut contract, &drop_id);
+
+    // The signer fails
+    set_caller(&contract_id());
+    assert!(contract
+        .resolve_multichain_mint(receiver_id(), drop_id.clone(), Err(PromiseError::Failed))
+        .is_none());
+    assert!(mint_status(&contract, &drop_id).state == MultichainMintState::Failed);
+
+    // The claimer retries and the same payload is signed again
+    set_caller(&receiver_id());
+    contract.retry_multichain_mint(drop_id.clone());
+    let requests = sign_requests();
+    assert_eq!(requests.len(), 1);
+    let payload: Vec<u8> = serde_json::from_value(requests[0]["payload"].clone()).unwrap();
+    assert_eq!(to_hex(&payload), EXPECTED_DIGEST);
+
+    let status = mint_status(&contract, &drop_id);
+    assert!(status.state == MultichainMintState::Pending);
+    assert_eq!(status.attempts, 2);
+
+    set_caller(&contract_id());
+    contract.resolve_multichain_mint(receiver_id(), drop_id.clone(), Ok(mock_signature()));
+    assert!(mint_status(&contract, &drop_id).state == MultichainMintState::Signed);
+}
+
+#[test]
+fn pending_mint_can_be_retried_after_timeout() {
+    let mut contract = setup_contract();
+    let drop_id = create_multichain_drop(&mut contract, NFT_CONTRACT);
+    add_account(&mut contract, &receiver_id());
+    mint(&mut contract, &drop_id);
+
+    // The signer never responds
+    set_caller_at(&receiver_id(), MULTICHAIN_MINT_TIMEOUT + 1);
+    contract.retry_multichain_mint(drop_id.clone());
+    assert_eq!(sign_requests().len(), 1);
+
+    let status = mint_status(&contract, &drop_id);
+    assert!(status.state == MultichainMintState::Pending);
+    assert_eq!(status.attempts, 2);
+    assert_eq!(status.requested_at, MULTICHAIN_MINT_TIMEOUT + 1);
+
+    // A late failure from the first request doesn't undo a signature from the retry
+    set_caller(&contract_id());
+    contract.resolve_multichain_mint(receiver_id(), drop_id.clone(), Ok(mock_signature()));
+    contract.resolve_multichain_mint(receiver_id(), drop_id.clone(), Err(PromiseError::Failed));
+    assert!(mint_status(&contract, &drop_id).state == MultichainMintState::Signed);
+}
+
+#[test]
+#[should_panic(expected = "Only failed or timed out multichain mints can be retried")]
+fn pending_mint_cannot_be_retried_before_timeout() {
+    let mut contract = setup_contract();
+    let drop_id = create_multichain_drop(&mut contract, NFT_CONTRACT);
+    add_account(&mut contract, &receiver_id());
+    mint(&mut contract, &drop_id);
+
+    set_caller_at(&receiver_id(), MULTICHAIN_MINT_TIMEOUT);
+    contract.retry_multichain_mint(drop_id);
+}
+
+#[test]
+fn admin_can_retry_pending_mint() {
+    let mut contract = setup_contract();
+    let drop_id = create_multichain_drop(&mut contract, NFT_CONTRACT);
+    add_account(&mut contract, &receiver_id());
+    mint(&mut contract, &drop_id);
+
+    set_caller(&admin_id());
+    contract.admin_retry_multichain_mint(receiver_id(), drop_id.clone());
+    assert_eq!(sign_requests().len(), 1);
+    assert_eq!(mint_status(&contract, &drop_id).attempts, 2);
+}
+
+#[test]
+#[should_panic(expected = "Multichain mint has already been signed")]
+fn admin_cannot_retry_signed_mint() {
+    let mut contract = setup_contract();
+    let drop_id = create_multichain_drop(&mut contract, NFT_CONTRACT);
+    add_account(&mut contract, &receiver_id());
+    mint(&mut contract, &drop_id);
+
+    set_caller(&contract_id());
+    contract.resolve_multichain_mint(receiver_id(), drop_id.clone(), Ok(mock_signature()));
+
+    set_caller(&admin_id());
+    contract.admin_retry_multichain_mint(receiver_id(), drop_id);
+}
@@ -0,0 +1,189 @@
+use super::*;
+
+fn alice_id() -> AccountId {
+    "alice.testnet".parse().unwrap()
+}
+
+fn sponsor_id() -> AccountId {
+    "sponsor.testnet".parse().unwrap()
+}
+
+/// Creates a sponsor token drop whose key is `keypair(0)`.
+fn create_signed_drop(contract: &mut Contract, legacy_claim_signatures: bool) -> DropId {
+    add_sponsor(contract, &sponsor_id(), 1000);
+    set_caller(&sponsor_id());
+    contract.create_token_drop(
+        "image".to_string(),
+        "Tokens".to_string(),
+        None,
+        keypair(0).1,
+        U128(10),
+        Some(DropConfig {
+            max_claims: Some(10),
+            legacy_claim_signatures,
+            ..Default::default()
+        }),
+    )
+}
+
+/// Signs the legacy `"{caller_id},{key}"` claim message.
+fn legacy_signature(signing_key: &SigningKey, claimer_id: &AccountId) -> Base64VecU8 {
+    let message = format!(
+        "{},{}",
+        claimer_id,
+        bs58::encode(signing_key.verifying_key().to_bytes()).into_string()
+    );
+    Base64VecU8(signing_key.sign(message.as_bytes()).to_bytes().to_vec())
+}
+
+#[test]
+fn claims_bump_the_claim_nonce() {
+    let mut contract = setup_contract();
+    let drop_id = create_signed_drop(&mut contract, false);
+
+    assert_eq!(contract.get_claim_nonce(alice_id()), 0);
+    claim(&mut contract, &alice_id(), &drop_id, &keypair(0).0, None);
+    assert_eq!(contract.get_claim_nonce(alice_id()), 1);
+}
+
+#[test]
+#[should_panic(expected = "Invalid signature")]
+fn claim_signatures_cannot_be_replayed() {
+    let mut contract = setup_contract();
+    let drop_id = create_signed_drop(&mut contract, false);
+    let (signing_key, public_key) = keypair(0);
+
+    // Sign the message for the current nonce and submit it twice
+    let expires_at = 1_000_000;
+    let message = format!(
+        "{},{},{},{},{},{}",
+        CLAIM_MESSAGE_VERSION,
+        drop_id,
+        alice_id(),
+        bs58::encode(&public_key.as_bytes()[1..]).into_string(),
+        0,
+        expires_at
+    );
+    let signature = Base64VecU8(signing_key.sign(message.as_bytes()).to_bytes().to_vec());
+    set_caller(&alice_id());
+    contract.assert_valid_signature(&drop_id, &alice_id(), &signature, None, Some(expires_at));
+    contract.assert_valid_signature(&drop_id, &alice_id(), &signature, None, Some(expires_at));
+}
+
+#[test]
+#[should_panic(expected = "Claim signature has expired")]
+fn expired_claim_signatures_are_rejected() {
+    let mut contract = setup_contract();
+    let drop_id = create_signed_drop(&mut contract, false);
+
+    set_caller_at(&alice_id(), 1_000_001);
+    contract.assert_valid_signature(
+        &drop_id,
+        &alice_id(),
+        &Base64VecU8(vec![0; 64]),
+        None,
+        Some(1_000_000),
+    );
+}
+
+#[test]
+#[should_panic(expected = "Drop requires a claim signature with an expiry")]
+fn legacy_claim_signatures_require_opt_in() {
+    let mut contract = setup_contract();
+    let drop_id = create_signed_drop(&mut contract, false);
+
+    set_caller(&alice_id());
+    let signature = legacy_signature(&keypair(0).0, &alice_id());
+    contract.claim_drop(drop_id, None, signature, None);
+}
+
+#[test]
+fn legacy_claim_signatures_are_accepted_when_enabled() {
+    let mut contract = setup_contract();
+    let drop_id = create_signed_drop(&mut contract, true);
+
+    set_caller(&alice_id());
+    let signature = legacy_signature(&keypair(0).0, &alice_id());
+    contract.claim_drop(drop_id, None, signature, None);
+    assert_eq!(contract.ft_balance_of(alice_id()).as_yoctonear(), 10);
+}
+
+// Key and `personal_sign` signature of "Some data" from the web3.js `accounts.sign` documentation
+const ETH_PUBLIC_KEY: &str = "4e3b81af9c2234cad09d679ce6035ed1392347ce64ce405f5dcd36228a25de6e47fd35c4215d1edf53e6f83de344615ce719bdb0fd878f6ed76f06dd277956de";
+const ETH_MESSAGE_HASH: &str = "1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655";
+const ETH_SIGNATURE: &str = "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+// `personal_sign` signature of a claim of drop `sponsor.testnet||0` by `alice.testnet` with nonce 0
+// that expires at 1000000, by the same key
+const ETH_CLAIM_SIGNATURE: &str = "44b39de191216fb3878949f31b592aff39636bfcceefc499077bff78664e50eb5089372e1ce05973009b179916fe2756d5caaa66421b40526791f3cc1f5204d41b";
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn eth_public_key() -> PublicKey {
+    PublicKey::from_parts(CurveType::SECP256K1, from_hex(ETH_PUBLIC_KEY)).unwrap()
+}
+
+#[test]
+fn eip191_hash_matches_personal_sign() {
+    set_caller(&contract_id());
+    assert_eq!(
+        eip191_hash(b"Some data").to_vec(),
+        from_hex(ETH_MESSAGE_HASH)
+    );
+}
+
+#[test]
+fn verifies_known_ethereum_signature() {
+    set_caller(&contract_id());
+    let public_key = from_hex(ETH_PUBLIC_KEY);
+    assert!(verify_secp256k1_signature(
+        from_hex(ETH_SIGNATURE),
+        b"Some data",
+        &public_key
+    ));
+
+    // Raw recovery IDs are accepted as well
+    let mut signature = from_hex(ETH_SIGNATURE);
+    signature[64] -= 27;
+    assert!(verify_secp256k1_signature(
+        signature,
+        b"Some data",
+        &public_key
+    ));
+
+    assert!(!verify_secp256k1_signature(
+        from_hex(ETH_SIGNATURE),
+        b"Other data",
+        &public_key
+    ));
+}
+
+#[test]
+fn verifies_claim_signed_by_ethereum_wallet() {
+    set_caller(&contract_id());
+    let params = || ClaimSignatureParams {
+        drop_id: "sponsor.testnet||0".to_string(),
+        nonce: 0,
+        expires_at: 1_000_000,
+    };
+    let alice: AccountId = "alice.testnet".parse().unwrap();
+
+    assert!(verify_signature(
+        Base64VecU8(from_hex(ETH_CLAIM_SIGNATURE)),
+        alice.clone(),
+        eth_public_key(),
+        Some(params()),
+    ));
+
+    // The signature is bound to the claimer
+    assert!(!verify_signature(
+        Base64VecU8(from_hex(ETH_CLAIM_SIGNATURE)),
+        "bob.testnet".parse().unwrap(),
+        eth_public_key(),
+        Some(params()),
+    ));
+}
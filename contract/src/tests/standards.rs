@@ -0,0 +1,252 @@
+use near_sdk::mock::MockAction;
+use near_sdk::test_utils::{get_created_receipts, VMContextBuilder};
+use near_sdk::{testing_env, PromiseError};
+
+use super::*;
+
+fn alice_id() -> AccountId {
+    "alice.testnet".parse().unwrap()
+}
+
+fn receiver_id() -> AccountId {
+    "dex.testnet".parse().unwrap()
+}
+
+/// Sets up the blockchain context for a call made by the predecessor with a deposit attached.
+fn set_caller_with_deposit(predecessor: &AccountId, deposit: NearToken) {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(contract_id())
+        .predecessor_account_id(predecessor.clone())
+        .signer_account_id(predecessor.clone())
+        .attached_deposit(deposit)
+        .build());
+}
+
+fn one_yocto() -> NearToken {
+    NearToken::from_yoctonear(1)
+}
+
+/// NEAR transferred to the account by the receipts created so far.
+fn refunds_to(account_id: &AccountId) -> Vec<NearToken> {
+    get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id == *account_id)
+        .flat_map(|receipt| receipt.actions)
+        .filter_map(|action| match action {
+            MockAction::Transfer { deposit, .. } => Some(deposit),
+            _ => None,
+        })
+        .collect()
+}
+
+fn balance(contract: &Contract, account_id: &AccountId) -> u128 {
+    contract.ft_balance_of(account_id.clone()).as_yoctonear()
+}
+
+/// Registers alice with 100 tokens and the receiving contract with none.
+fn setup_accounts() -> Contract {
+    let mut contract = setup_contract();
+    add_account(&mut contract, &alice_id());
+    add_account(&mut contract, &receiver_id());
+    contract.internal_deposit_ft_mint(&alice_id(), NearToken::from_yoctonear(100), None, false);
+    contract
+}
+
+/// Calls `ft_transfer_call` from alice and checks that the receiver is called with the transfer.
+fn transfer_call(contract: &mut Contract, amount: u128) {
+    set_caller_with_deposit(&alice_id(), one_yocto());
+    let _ = contract.ft_transfer_call(receiver_id(), U128(amount), None, "swap".to_string());
+
+    let on_transfer_args: Vec<serde_json::Value> = get_created_receipts()
+        .into_iter()
+        .filter(|receipt| receipt.receiver_id == receiver_id())
+        .flat_map(|receipt| receipt.actions)
+        .filter_map(|action| match action {
+            MockAction::FunctionCallWeight {
+                method_name, args, ..
+            } if method_name == b"ft_on_transfer" => Some(serde_json::from_slice(&args).unwrap()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(on_transfer_args.len(), 1);
+    assert_eq!(on_transfer_args[0]["sender_id"], alice_id().to_string());
+    assert_eq!(on_transfer_args[0]["amount"], amount.to_string());
+    assert_eq!(on_transfer_args[0]["msg"], "swap");
+}
+
+fn resolve_transfer(
+    contract: &mut Contract,
+    amount: u128,
+    unused_amount: Result<U128, PromiseError>,
+) -> u128 {
+    set_caller(&contract_id());
+    contract
+        .ft_resolve_transfer(alice_id(), receiver_id(), U128(amount), unused_amount)
+        .0
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn ft_transfer_requires_one_yocto() {
+    let mut contract = setup_accounts();
+    set_caller(&alice_id());
+    let _ = contract.ft_transfer(receiver_id(), U128(10), None);
+}
+
+#[test]
+#[should_panic(expected = "Requires attached deposit of exactly 1 yoctoNEAR")]
+fn ft_transfer_call_requires_one_yocto() {
+    let mut contract = setup_accounts();
+    set_caller_with_deposit(&alice_id(), NearToken::from_yoctonear(2));
+    let _ = contract.ft_transfer_call(receiver_id(), U128(10), None, String::new());
+}
+
+#[test]
+fn ft_transfer_with_one_yocto() {
+    let mut contract = setup_accounts();
+    set_caller_with_deposit(&alice_id(), one_yocto());
+    let _ = contract.ft_transfer(receiver_id(), U128(10), None);
+    assert_eq!(balance(&contract, &alice_id()), 90);
+    assert_eq!(balance(&contract, &receiver_id()), 10);
+}
+
+#[test]
+#[should_panic(expected = "Receiver not found in map")]
+fn ft_transfer_requires_registered_receiver() {
+    let mut contract = setup_accounts();
+    set_caller_with_deposit(&alice_id(), one_yocto());
+    let _ = contract.ft_transfer("bob.testnet".parse().unwrap(), U128(10), None);
+}
+
+#[test]
+fn ft_transfer_call_refunds_unused_tokens() {
+    let mut contract = setup_accounts();
+    transfer_call(&mut contract, 100);
+    assert_eq!(balance(&contract, &receiver_id()), 100);
+
+    assert_eq!(resolve_transfer(&mut contract, 100, Ok(U128(30))), 70);
+    assert_eq!(balance(&contract, &alice_id()), 30);
+    assert_eq!(balance(&contract, &receiver_id()), 70);
+    assert_eq!(contract.ft_total_supply().as_yoctonear(), 100);
+}
+
+#[test]
+fn ft_transfer_call_refunds_everything_if_the_receiver_fails() {
+    let mut contract = setup_accounts();
+    transfer_call(&mut contract, 40);
+
+    assert_eq!(
+        resolve_transfer(&mut contract, 40, Err(PromiseError::Failed)),
+        0
+    );
+    assert_eq!(balance(&contract, &alice_id()), 100);
+    assert_eq!(balance(&contract, &receiver_id()), 0);
+}
+
+#[test]
+fn ft_transfer_call_ignores_unused_amounts_above_the_transfer() {
+    let mut contract = setup_accounts();
+    transfer_call(&mut contract, 40);
+
+    assert_eq!(resolve_transfer(&mut contract, 40, Ok(U128(1000))), 0);
+    assert_eq!(balance(&contract, &alice_id()), 100);
+}
+
+#[test]
+fn ft_transfer_call_refund_is_limited_to_the_receiver_balance() {
+    let mut contract = setup_accounts();
+    transfer_call(&mut contract, 40);
+
+    // The receiver spends some of the tokens before the transfer is resolved
+    contract.internal_ft_transfer(
+        &receiver_id(),
+        &alice_id(),
+        NearToken::from_yoctonear(30),
+        false,
+        None,
+    );
+    assert_eq!(resolve_transfer(&mut contract, 40, Ok(U128(40))), 30);
+    assert_eq!(balance(&contract, &alice_id()), 100);
+    assert_eq!(balance(&contract, &receiver_id()), 0);
+}
+
+#[test]
+fn ft_transfer_call_burns_refunds_to_unregistered_senders() {
+    let mut contract = setup_accounts();
+    transfer_call(&mut contract, 40);
+    contract.account_details_by_id.remove(&alice_id());
+
+    assert_eq!(resolve_transfer(&mut contract, 40, Ok(U128(10))), 30);
+    assert_eq!(balance(&contract, &receiver_id()), 30);
+    assert_eq!(contract.ft_total_supply().as_yoctonear(), 90);
+}
+
+#[test]
+fn storage_deposit_registers_accounts() {
+    let mut contract = setup_contract();
+    let bob_id: AccountId = "bob.testnet".parse().unwrap();
+    assert!(contract.storage_balance_of(bob_id.clone()).is_none());
+
+    let bounds = contract.storage_balance_bounds();
+    assert_eq!(bounds.max, Some(bounds.min));
+
+    set_caller_with_deposit(&alice_id(), bounds.min.saturating_add(one_yocto()));
+    let storage_balance = contract.storage_deposit(Some(bob_id.clone()), None);
+    assert_eq!(storage_balance.total, bounds.min);
+    assert!(storage_balance.available.is_zero());
+
+    let storage_balance = contract.storage_balance_of(bob_id).unwrap();
+    assert_eq!(storage_balance.total, bounds.min);
+
+    // The deposit above the registration cost goes back to the caller
+    assert_eq!(refunds_to(&alice_id()), vec![one_yocto()]);
+}
+
+#[test]
+fn storage_deposit_registration_only_refunds_above_the_minimum() {
+    let mut contract = setup_contract();
+    let bounds = contract.storage_balance_bounds();
+    let excess = NearToken::from_millinear(5);
+
+    set_caller_with_deposit(&alice_id(), bounds.min.saturating_add(excess));
+    let storage_balance = contract.storage_deposit(None, Some(true));
+    assert_eq!(storage_balance.total, bounds.min);
+    assert!(storage_balance.available.is_zero());
+    assert_eq!(refunds_to(&alice_id()), vec![excess]);
+}
+
+#[test]
+fn storage_deposit_refunds_registered_accounts_in_full() {
+    let mut contract = setup_contract();
+    add_account(&mut contract, &alice_id());
+    let bounds = contract.storage_balance_bounds();
+
+    set_caller_with_deposit(&alice_id(), bounds.min);
+    let storage_balance = contract.storage_deposit(None, Some(true));
+    assert_eq!(storage_balance.total, bounds.min);
+    assert_eq!(refunds_to(&alice_id()), vec![bounds.min]);
+}
+
+#[test]
+fn storage_deposit_defaults_to_the_caller_and_refunds_registered_accounts() {
+    let mut contract = setup_contract();
+    let bounds = contract.storage_balance_bounds();
+
+    set_caller_with_deposit(&alice_id(), bounds.min);
+    contract.storage_deposit(None, None);
+    assert!(contract.storage_balance_of(alice_id()).is_some());
+
+    set_caller_with_deposit(&alice_id(), bounds.min);
+    contract.storage_deposit(None, Some(true));
+    assert_eq!(contract.account_details_by_id.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "The attached deposit is less than the minimum storage balance")]
+fn storage_deposit_requires_the_registration_cost() {
+    let mut contract = setup_contract();
+    let bounds = contract.storage_balance_bounds();
+
+    set_caller_with_deposit(&alice_id(), bounds.min.saturating_sub(one_yocto()));
+    contract.storage_deposit(None, None);
+}
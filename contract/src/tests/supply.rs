@@ -0,0 +1,135 @@
+use super::*;
+
+fn sponsor_id() -> AccountId {
+    "sponsor.testnet".parse().unwrap()
+}
+
+fn alice_id() -> AccountId {
+    "alice.testnet".parse().unwrap()
+}
+
+/// Checks that every token in the supply is held by an account, set aside in a drop budget or
+/// held on a ticket for its next account.
+fn assert_supply_is_backed(contract: &Contract) {
+    let balances: u128 = contract
+        .account_details_by_id
+        .values()
+        .map(|details| details.ft_balance.as_yoctonear())
+        .sum();
+    let budgets: u128 = contract
+        .drop_by_id
+        .values()
+        .filter_map(|drop| drop.get_budget())
+        .map(|budget| budget.as_yoctonear())
+        .sum();
+    let held: u128 = contract
+        .attendee_ticket_by_pk
+        .values()
+        .map(|ticket| ticket.held_tokens.as_yoctonear())
+        .sum();
+    assert_eq!(
+        contract.ft_total_supply().as_yoctonear(),
+        balances + budgets + held
+    );
+}
+
+/// Sets up a sponsor with a funded token drop and alice with some minted tokens.
+fn setup_supply() -> (Contract, SigningKey, DropId) {
+    let mut contract = setup_contract();
+    add_sponsor(&mut contract, &sponsor_id(), 1000);
+    add_account(&mut contract, &alice_id());
+
+    set_caller(&admin_id());
+    contract.ft_mint(alice_id(), U128(50));
+    assert_supply_is_backed(&contract);
+
+    let (drop_key, drop_pk) = keypair(1);
+    set_caller(&sponsor_id());
+    let drop_id = contract.create_token_drop(
+        "image".to_string(),
+        "Tokens".to_string(),
+        None,
+        drop_pk,
+        U128(100),
+        Some(DropConfig {
+            max_claims: Some(3),
+            ..Default::default()
+        }),
+    );
+    assert_supply_is_backed(&contract);
+    (contract, drop_key, drop_id)
+}
+
+/// Sets up the context for a call from alice with the 1 yoctoNEAR deposit burns need.
+fn set_alice_burning() {
+    testing_env!(VMContextBuilder::new()
+        .current_account_id(contract_id())
+        .predecessor_account_id(alice_id())
+        .attached_deposit(NearToken::from_yoctonear(1))
+        .build());
+}
+
+#[test]
+fn supply_is_backed_after_minting_and_claiming() {
+    let (mut contract, drop_key, drop_id) = setup_supply();
+    assert_eq!(contract.ft_total_supply().as_yoctonear(), 1050);
+
+    claim(&mut contract, &alice_id(), &drop_id, &drop_key, None);
+    assert_eq!(contract.ft_balance_of(alice_id()).as_yoctonear(), 150);
+    assert_supply_is_backed(&contract);
+}
+
+#[test]
+fn supply_is_backed_after_burning() {
+    let (mut contract, _, _) = setup_supply();
+
+    set_alice_burning();
+    contract.ft_burn(U128(20), None);
+
+    set_caller(&admin_id());
+    contract.ft_burn_from(sponsor_id(), U128(100), None);
+
+    assert_eq!(contract.ft_total_supply().as_yoctonear(), 930);
+    assert_supply_is_backed(&contract);
+}
+
+#[test]
+#[should_panic(expected = "The conference is over, only NFT assets can be transacted with")]
+fn tokens_cannot_be_burned_once_frozen() {
+    let (mut contract, _, _) = setup_supply();
+    freeze(&mut contract);
+
+    set_alice_burning();
+    contract.ft_burn(U128(20), None);
+}
+
+#[test]
+#[should_panic(expected = "The conference is over, only NFT assets can be transacted with")]
+fn admins_cannot_burn_tokens_once_frozen() {
+    let (mut contract, _, _) = setup_supply();
+    freeze(&mut contract);
+
+    set_caller(&admin_id());
+    contract.ft_burn_from(sponsor_id(), U128(100), None);
+}
+
+#[test]
+fn supply_is_backed_after_offboarding_and_clearing() {
+    let (mut contract, drop_key, drop_id) = setup_supply();
+    claim(&mut contract, &alice_id(), &drop_id, &drop_key, None);
+    freeze(&mut contract);
+
+    set_caller(&alice_id());
+    contract.offboard(admin_id());
+    assert_eq!(contract.ft_balance_of(alice_id()).as_yoctonear(), 0);
+    assert_supply_is_backed(&contract);
+
+    let mut contract = commit(contract);
+    set_caller(&contract_id());
+    contract.clear_storage(None, contract_id(), None);
+    assert_supply_is_backed(&contract);
+
+    contract.clear_storage(None, contract_id(), Some(true));
+    assert_eq!(contract.ft_total_supply().as_yoctonear(), 0);
+    assert_supply_is_backed(&contract);
+}
@@ -0,0 +1,606 @@
+use near_sdk::mock::MockAction;
+use near_sdk::test_utils::get_created_receipts;
+
+use super::*;
+use crate::tickets::add_tickets::KeyData;
+
+fn sponsor_id() -> AccountId {
+    "sponsor.testnet".parse().unwrap()
+}
+
+fn ticket_key(seed: &str) -> PublicKey {
+    format!("ed25519:{}", seed).parse().unwrap()
+}
+
+fn add_ticket(contract: &mut Contract, public_key: PublicKey, account_id: Option<AccountId>) {
+    contract.attendee_ticket_by_pk.insert(
+        public_key,
+        AttendeeTicketInformation {
+            check_ins: account_id
+                .iter()
+                .map(|_| TicketCheckIn {
+                    session: DEFAULT_SESSION.to_string(),
+                    scanned_at: 0,
+                    scanned_by: None,
+                    location: None,
+                })
+                .collect(),
+            reserved_account_id: None,
+            drop_id: Some("ticket".to_string()),
+            account_id,
+            metadata: None,
+            held_tokens: NearToken::from_yoctonear(0),
+            held_nft_ids: Vec::new(),
+        },
+    );
+}
+
+/// Method names of every key re-added by the receipts created so far, in order.
+fn refreshed_method_names() -> Vec<String> {
+    let mut deleted = 0;
+    let mut method_names = vec![];
+    for receipt in get_created_receipts() {
+        for action in receipt.actions {
+            match action {
+                MockAction::DeleteKey { .. } => deleted += 1,
+                MockAction::AddKeyWithFunctionCall {
+                    method_names: names,
+                    ..
+                } => method_names.push(names.join(",")),
+                _ => {}
+            }
+        }
+    }
+    assert_eq!(deleted, method_names.len());
+    method_names
+}
+
+fn setup_tickets() -> Contract {
+    let mut contract = setup_contract();
+    add_ticket(
+        &mut contract,
+        ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e"),
+        None,
+    );
+    add_account(&mut contract, &sponsor_id());
+    contract
+        .account_details_by_id
+        .get_mut(&sponsor_id())
+        .unwrap()
+        .account_status = Some(AccountStatus::Sponsor);
+    add_ticket(
+        &mut contract,
+        ticket_key("H8tBNhpsXj8TC6LgmJXpTZsh5Vz2Zh9r6NYQzZYZ8Kzm"),
+        Some(sponsor_id()),
+    );
+    add_ticket(&mut contract, contract_key(), Some(contract_id()));
+    contract
+}
+
+#[test]
+fn refresh_re_adds_keys_with_current_method_names() {
+    let mut contract = setup_tickets();
+
+    set_caller(&admin_id());
+    assert_eq!(contract.refresh_ticket_keys(None, None), 0);
+
+    // The contract's own key is never touched
+    assert_eq!(
+        refreshed_method_names(),
+        vec![
+            ATTENDEE_KEY_METHOD_NAMES.to_string(),
+            SPONSOR_KEY_METHOD_NAMES.to_string(),
+        ]
+    );
+}
+
+#[test]
+fn refresh_is_paginated() {
+    let mut contract = setup_tickets();
+
+    set_caller(&admin_id());
+    assert_eq!(contract.refresh_ticket_keys(None, Some(1)), 2);
+    assert_eq!(
+        refreshed_method_names(),
+        vec![ATTENDEE_KEY_METHOD_NAMES.to_string()]
+    );
+
+    set_caller(&admin_id());
+    assert_eq!(contract.refresh_ticket_keys(Some(1), Some(1)), 1);
+    assert_eq!(
+        refreshed_method_names(),
+        vec![SPONSOR_KEY_METHOD_NAMES.to_string()]
+    );
+}
+
+#[test]
+fn refresh_works_while_frozen() {
+    let mut contract = setup_tickets();
+    contract.is_contract_frozen = true;
+
+    set_caller(&admin_id());
+    assert_eq!(contract.refresh_ticket_keys(None, None), 0);
+    assert_eq!(refreshed_method_names().len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Maximum number of keys exceeded")]
+fn refresh_limit_is_capped() {
+    let mut contract = setup_tickets();
+
+    set_caller(&admin_id());
+    contract.refresh_ticket_keys(None, Some(MAX_KEYS_PER_REFRESH + 1));
+}
+
+#[test]
+#[should_panic(expected = "No account status found")]
+fn only_admins_can_refresh_keys() {
+    let mut contract = setup_tickets();
+    let attendee_id: AccountId = "attendee.testnet".parse().unwrap();
+    add_account(&mut contract, &attendee_id);
+
+    set_caller(&attendee_id);
+    contract.refresh_ticket_keys(None, None);
+}
+
+#[test]
+fn revoking_a_ticket_deletes_its_key() {
+    let mut contract = setup_tickets();
+    let public_key = ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e");
+
+    set_caller(&admin_id());
+    contract.revoke_ticket(public_key.clone());
+    assert!(contract.attendee_ticket_by_pk.get(&public_key).is_none());
+    let actions: Vec<_> = get_created_receipts()
+        .into_iter()
+        .flat_map(|receipt| receipt.actions)
+        .collect();
+    assert!(matches!(actions.as_slice(), [MockAction::DeleteKey { .. }]));
+}
+
+#[test]
+fn rotating_a_ticket_key_keeps_its_permissions() {
+    let mut contract = setup_tickets();
+    let old_pk = ticket_key("H8tBNhpsXj8TC6LgmJXpTZsh5Vz2Zh9r6NYQzZYZ8Kzm");
+    let (_, new_pk) = keypair(1);
+
+    set_caller(&admin_id());
+    contract.rotate_ticket_key(old_pk.clone(), new_pk.clone());
+    assert!(contract.attendee_ticket_by_pk.get(&old_pk).is_none());
+    assert_eq!(
+        contract
+            .attendee_ticket_by_pk
+            .get(&new_pk)
+            .unwrap()
+            .account_id,
+        Some(sponsor_id())
+    );
+    assert_eq!(
+        refreshed_method_names(),
+        vec![SPONSOR_KEY_METHOD_NAMES.to_string()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Cannot revoke the contract key")]
+fn contract_key_cannot_be_revoked() {
+    let mut contract = setup_tickets();
+
+    set_caller(&admin_id());
+    contract.revoke_ticket(contract_key());
+}
+
+#[test]
+fn transferring_a_ticket_moves_it_to_the_new_key() {
+    let mut contract = setup_tickets();
+    let old_pk = ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e");
+    let (_, new_pk) = keypair(1);
+
+    set_ticket_signer(&old_pk);
+    contract.transfer_ticket(new_pk.clone(), Some("metadata".to_string()));
+    assert!(contract.attendee_ticket_by_pk.get(&old_pk).is_none());
+    assert_eq!(
+        contract
+            .attendee_ticket_by_pk
+            .get(&new_pk)
+            .unwrap()
+            .metadata,
+        Some("metadata".to_string())
+    );
+    assert_eq!(
+        refreshed_method_names(),
+        vec![ATTENDEE_KEY_METHOD_NAMES.to_string()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Ticket has already been scanned")]
+fn scanned_tickets_cannot_be_transferred() {
+    let mut contract = setup_tickets();
+
+    set_ticket_signer(&ticket_key("H8tBNhpsXj8TC6LgmJXpTZsh5Vz2Zh9r6NYQzZYZ8Kzm"));
+    contract.transfer_ticket(keypair(1).1, None);
+}
+
+fn scanner_id() -> AccountId {
+    "scanner.testnet".parse().unwrap()
+}
+
+#[test]
+fn staff_scans_are_recorded_per_location() {
+    let mut contract = setup_tickets();
+    let mut account_details = AccountDetails::new(&scanner_id());
+    account_details.account_status = Some(AccountStatus::Scanner);
+    contract
+        .account_details_by_id
+        .insert(scanner_id(), account_details);
+    let ticket_pk = ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e");
+
+    set_caller_at(&scanner_id(), 42);
+    contract.scan_ticket_by_staff(ticket_pk.clone(), "Gate A".to_string(), None);
+
+    let ticket = contract.attendee_ticket_by_pk.get(&ticket_pk).unwrap();
+    let check_in = &ticket.check_ins[0];
+    assert_eq!(check_in.session, DEFAULT_SESSION);
+    assert_eq!(check_in.scanned_by, Some(scanner_id()));
+    assert_eq!(check_in.location, Some("Gate A".to_string()));
+    assert_eq!(check_in.scanned_at, 42);
+    assert_eq!(
+        contract.get_check_ins_by_location(),
+        vec![("Gate A".to_string(), 1)]
+    );
+}
+
+#[test]
+#[should_panic(expected = "No account status found")]
+fn only_scanners_can_scan_tickets() {
+    let mut contract = setup_tickets();
+    add_account(&mut contract, &scanner_id());
+
+    set_caller(&scanner_id());
+    contract.scan_ticket_by_staff(
+        ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e"),
+        "Gate A".to_string(),
+        None,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Tickets must be scanned by check-in staff")]
+fn tickets_cannot_scan_themselves_by_default() {
+    let mut contract = setup_tickets();
+
+    set_ticket_signer(&ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e"));
+    contract.scan_ticket(None);
+}
+
+#[test]
+fn admin_can_allow_self_check_in() {
+    let mut contract = setup_tickets();
+    let ticket_pk = ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e");
+
+    set_caller(&admin_id());
+    contract.set_allow_self_check_in(true);
+    assert!(contract.is_self_check_in_allowed());
+
+    set_ticket_signer(&ticket_pk);
+    contract.scan_ticket(None);
+    assert!(contract
+        .attendee_ticket_by_pk
+        .get(&ticket_pk)
+        .unwrap()
+        .has_scanned());
+}
+
+/// Adds a ticket type that only grants access to the first day and a ticket of that type.
+fn add_day_one_ticket(contract: &mut Contract) -> PublicKey {
+    contract.ticket_data_by_id.insert(
+        "day-1".to_string(),
+        TicketType {
+            starting_near_balance: NearToken::from_yoctonear(0),
+            starting_token_balance: NearToken::from_yoctonear(0),
+            account_type: AccountStatus::Basic,
+            sessions: Some(vec!["day-1".to_string()]),
+            max_supply: None,
+            wallet_version: None,
+        },
+    );
+    let (_, ticket_pk) = keypair(1);
+    contract.attendee_ticket_by_pk.insert(
+        ticket_pk.clone(),
+        AttendeeTicketInformation {
+            check_ins: vec![],
+            reserved_account_id: None,
+            drop_id: Some("day-1".to_string()),
+            account_id: None,
+            metadata: None,
+            held_tokens: NearToken::from_yoctonear(0),
+            held_nft_ids: Vec::new(),
+        },
+    );
+    ticket_pk
+}
+
+#[test]
+fn tickets_check_into_each_session_once() {
+    let mut contract = setup_tickets();
+    let ticket_pk = add_day_one_ticket(&mut contract);
+
+    set_caller(&admin_id());
+    contract.scan_ticket_by_staff(
+        ticket_pk.clone(),
+        "Gate A".to_string(),
+        Some("day-1".to_string()),
+    );
+    assert_eq!(contract.get_session_attendance("day-1".to_string()), 1);
+    assert!(contract
+        .attendee_ticket_by_pk
+        .get(&ticket_pk)
+        .unwrap()
+        .has_checked_in("day-1"));
+}
+
+#[test]
+#[should_panic(expected = "Ticket does not grant access to this session")]
+fn tickets_only_check_into_their_sessions() {
+    let mut contract = setup_tickets();
+    let ticket_pk = add_day_one_ticket(&mut contract);
+
+    set_caller(&admin_id());
+    contract.scan_ticket_by_staff(ticket_pk, "Gate A".to_string(), None);
+}
+
+#[test]
+#[should_panic(expected = "Unknown session")]
+fn tickets_cannot_check_into_unknown_sessions() {
+    let mut contract = setup_tickets();
+    let ticket_pk = add_day_one_ticket(&mut contract);
+
+    set_caller(&admin_id());
+    contract.scan_ticket_by_staff(ticket_pk, "Gate A".to_string(), Some("day-9".to_string()));
+}
+
+fn ticket_type_with(starting_tokens: u128, account_type: AccountStatus) -> TicketType {
+    TicketType {
+        starting_near_balance: NearToken::from_yoctonear(0),
+        starting_token_balance: NearToken::from_yoctonear(starting_tokens),
+        account_type,
+        sessions: None,
+        max_supply: None,
+        wallet_version: None,
+    }
+}
+
+#[test]
+fn upgrading_a_ticket_tops_up_tokens_and_permissions() {
+    let mut contract = setup_tickets();
+    contract.ticket_data_by_id.insert(
+        "basic".to_string(),
+        ticket_type_with(100, AccountStatus::Basic),
+    );
+    contract.ticket_data_by_id.insert(
+        "vip".to_string(),
+        ticket_type_with(250, AccountStatus::Sponsor),
+    );
+    let attendee_id: AccountId = "attendee.testnet".parse().unwrap();
+    add_account(&mut contract, &attendee_id);
+    let (_, ticket_pk) = keypair(1);
+    contract.attendee_ticket_by_pk.insert(
+        ticket_pk.clone(),
+        AttendeeTicketInformation {
+            check_ins: vec![],
+            reserved_account_id: None,
+            drop_id: Some("basic".to_string()),
+            account_id: Some(attendee_id.clone()),
+            metadata: None,
+            held_tokens: NearToken::from_yoctonear(0),
+            held_nft_ids: Vec::new(),
+        },
+    );
+
+    set_caller(&admin_id());
+    contract.upgrade_ticket(ticket_pk.clone(), "vip".to_string());
+
+    assert_eq!(
+        contract
+            .attendee_ticket_by_pk
+            .get(&ticket_pk)
+            .unwrap()
+            .drop_id,
+        Some("vip".to_string())
+    );
+    assert_eq!(
+        contract.ft_balance_of(attendee_id.clone()).as_yoctonear(),
+        150
+    );
+    assert!(matches!(
+        contract
+            .account_details_by_id
+            .get(&attendee_id)
+            .unwrap()
+            .account_status,
+        Some(AccountStatus::Sponsor)
+    ));
+    assert_eq!(
+        refreshed_method_names(),
+        vec![SPONSOR_KEY_METHOD_NAMES.to_string()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Ticket already has this ticket type")]
+fn tickets_cannot_be_upgraded_to_their_own_type() {
+    let mut contract = setup_tickets();
+    contract.ticket_data_by_id.insert(
+        "ticket".to_string(),
+        ticket_type_with(100, AccountStatus::Basic),
+    );
+
+    set_caller(&admin_id());
+    contract.upgrade_ticket(
+        ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e"),
+        "ticket".to_string(),
+    );
+}
+
+fn ticket_stats(contract: &Contract, drop_id: &str) -> TicketTypeStats {
+    contract
+        .ticket_stats_by_id
+        .get(drop_id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Adds a ticket type with a maximum supply and issues tickets of that type for the keys.
+fn issue_tickets(contract: &mut Contract, max_supply: Option<u64>, keys: &[PublicKey]) {
+    contract.ticket_data_by_id.insert(
+        "ga".to_string(),
+        TicketType {
+            max_supply,
+            ..ticket_type_with(100, AccountStatus::Basic)
+        },
+    );
+    set_caller(&admin_id());
+    contract.add_tickets(
+        "ga".to_string(),
+        keys.iter()
+            .map(|public_key| KeyData {
+                public_key: public_key.clone(),
+                metadata: None,
+            })
+            .collect(),
+    );
+}
+
+#[test]
+fn ticket_stats_track_issued_and_scanned_tickets() {
+    let mut contract = setup_tickets();
+    let (_, scanned_pk) = keypair(1);
+    let (_, unscanned_pk) = keypair(2);
+    issue_tickets(
+        &mut contract,
+        Some(2),
+        &[scanned_pk.clone(), unscanned_pk.clone()],
+    );
+
+    contract.scan_ticket_by_staff(scanned_pk.clone(), "Gate A".to_string(), None);
+    assert_eq!(ticket_stats(&contract, "ga").issued, 2);
+    assert_eq!(ticket_stats(&contract, "ga").scanned, 1);
+
+    let stats = &contract.get_ticket_type_stats()[0];
+    assert_eq!(stats.max_supply, Some(2));
+    assert_eq!(stats.issued, 2);
+}
+
+#[test]
+fn revoking_a_ticket_updates_its_stats() {
+    let mut contract = setup_tickets();
+    let (_, scanned_pk) = keypair(1);
+    let (_, unscanned_pk) = keypair(2);
+    issue_tickets(
+        &mut contract,
+        None,
+        &[scanned_pk.clone(), unscanned_pk.clone()],
+    );
+    contract.scan_ticket_by_staff(scanned_pk.clone(), "Gate A".to_string(), None);
+
+    contract.revoke_ticket(unscanned_pk);
+    assert_eq!(ticket_stats(&contract, "ga").issued, 1);
+    assert_eq!(ticket_stats(&contract, "ga").scanned, 1);
+
+    contract.revoke_ticket(scanned_pk);
+    assert_eq!(ticket_stats(&contract, "ga").issued, 0);
+    assert_eq!(ticket_stats(&contract, "ga").scanned, 0);
+}
+
+#[test]
+#[should_panic(expected = "Maximum supply for this ticket type exceeded")]
+fn tickets_cannot_exceed_max_supply() {
+    let mut contract = setup_tickets();
+    issue_tickets(&mut contract, Some(1), &[keypair(1).1, keypair(2).1]);
+}
+
+#[test]
+#[should_panic(expected = "Max supply cannot be lower than the number of tickets already issued")]
+fn max_supply_cannot_drop_below_issued_tickets() {
+    let mut contract = setup_tickets();
+    issue_tickets(&mut contract, None, &[keypair(1).1, keypair(2).1]);
+
+    contract.update_ticket_data(
+        "ga".to_string(),
+        TicketType {
+            max_supply: Some(1),
+            wallet_version: None,
+            ..ticket_type_with(100, AccountStatus::Basic)
+        },
+    );
+}
+
+#[test]
+fn transferring_a_ticket_releases_its_account_name() {
+    let mut contract = setup_tickets();
+    let ticket_pk = ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e");
+    let (_, new_pk) = keypair(1);
+
+    set_ticket_signer(&ticket_pk);
+    let account_id = contract.reserve_account_name("alice".parse().unwrap());
+    assert!(!contract.check_account_name_available(account_id.clone()));
+
+    contract.transfer_ticket(new_pk.clone(), None);
+    assert!(contract.check_account_name_available(account_id));
+    assert!(contract
+        .attendee_ticket_by_pk
+        .get(&new_pk)
+        .unwrap()
+        .reserved_account_id
+        .is_none());
+}
+
+#[test]
+fn reserving_a_new_name_releases_the_previous_one() {
+    let mut contract = setup_tickets();
+    let ticket_pk = ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e");
+
+    set_ticket_signer(&ticket_pk);
+    let alice = contract.reserve_account_name("alice".parse().unwrap());
+    assert_eq!(alice.as_str(), format!("alice.{}", contract_id()));
+    let bob = contract.reserve_account_name(format!("bob.{}", contract_id()).parse().unwrap());
+
+    assert!(contract.check_account_name_available(alice));
+    assert!(!contract.check_account_name_available(bob));
+}
+
+#[test]
+#[should_panic(expected = "Account name is not available")]
+fn reserved_account_names_cannot_be_taken() {
+    let mut contract = setup_tickets();
+    let (_, other_pk) = keypair(1);
+    add_ticket(&mut contract, other_pk.clone(), None);
+
+    set_ticket_signer(&ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e"));
+    contract.reserve_account_name("alice".parse().unwrap());
+
+    set_ticket_signer(&other_pk);
+    contract.reserve_account_name("alice".parse().unwrap());
+}
+
+#[test]
+fn revoking_a_ticket_releases_its_account_name() {
+    let mut contract = setup_tickets();
+    let ticket_pk = ticket_key("DgJ7Sx5K7vuBMCnxSMuxzgWxXNZPBQGUH3wjnU6A4w8e");
+
+    set_ticket_signer(&ticket_pk);
+    let account_id = contract.reserve_account_name("alice".parse().unwrap());
+
+    set_caller(&admin_id());
+    contract.revoke_ticket(ticket_pk);
+    assert!(contract.check_account_name_available(account_id));
+}
+
+#[test]
+fn check_account_name_available_rejects_nested_names() {
+    set_caller(&contract_id());
+    let contract = setup_contract();
+    assert!(contract.check_account_name_available("alice".parse().unwrap()));
+    assert!(!contract.check_account_name_available("a.b".parse().unwrap()));
+}
@@ -0,0 +1,89 @@
+use super::*;
+
+fn vendor_id() -> AccountId {
+    "vendor.testnet".parse().unwrap()
+}
+
+fn buyer_id() -> AccountId {
+    "alice.testnet".parse().unwrap()
+}
+
+fn item(id: &str, price: u128, stock: u64) -> VendorItem {
+    VendorItem {
+        id: id.to_string(),
+        name: id.to_string(),
+        price: NearToken::from_yoctonear(price),
+        stock,
+        image: None,
+    }
+}
+
+fn setup_vendor() -> Contract {
+    let mut contract = setup_contract();
+    let mut vendor_details = AccountDetails::new(&vendor_id());
+    vendor_details.account_status = Some(AccountStatus::Vendor);
+    contract
+        .account_details_by_id
+        .insert(vendor_id(), vendor_details);
+    add_account(&mut contract, &buyer_id());
+    contract.internal_deposit_ft_mint(&buyer_id(), NearToken::from_yoctonear(100), None, false);
+    contract
+}
+
+#[test]
+fn purchases_update_the_vendor_catalog_and_sales() {
+    let mut contract = setup_vendor();
+    set_caller(&vendor_id());
+    contract.set_vendor_items(vec![item("coffee", 5, 10), item("shirt", 20, 1)]);
+
+    set_caller(&buyer_id());
+    let total = contract.purchase_items(
+        vendor_id(),
+        vec![("coffee".to_string(), 2), ("shirt".to_string(), 1)],
+    );
+    assert_eq!(total.0, 30);
+    assert_eq!(contract.ft_balance_of(vendor_id()).as_yoctonear(), 30);
+
+    let items = contract.get_vendor_items(vendor_id());
+    let coffee = items.iter().find(|item| item.id == "coffee").unwrap();
+    assert_eq!(coffee.stock, 8);
+
+    let sales = contract.get_vendor_sales(vendor_id());
+    assert_eq!(sales.total_revenue.as_yoctonear(), 30);
+    assert_eq!(sales.num_purchases, 1);
+    assert_eq!(sales.sales_by_item.len(), 2);
+
+    // Removing an item keeps its sales
+    set_caller(&vendor_id());
+    contract.remove_vendor_items(vec!["shirt".to_string()]);
+    assert_eq!(contract.get_vendor_items(vendor_id()).len(), 1);
+    assert_eq!(
+        contract.get_vendor_sales(vendor_id()).sales_by_item.len(),
+        2
+    );
+}
+
+#[test]
+fn clear_storage_clears_vendor_catalogs() {
+    let mut contract = setup_vendor();
+    set_caller(&vendor_id());
+    contract.set_vendor_items(vec![item("coffee", 5, 10)]);
+    set_caller(&buyer_id());
+    contract.purchase_items(vendor_id(), vec![("coffee".to_string(), 1)]);
+    let mut contract = commit(contract);
+
+    // A fresh handle on the vendor's maps reads the entries straight from storage
+    let stored = VendorInformation::new(&vendor_id());
+    assert!(stored.items.get("coffee").is_some());
+    assert!(stored.sales_by_item.get("coffee").is_some());
+
+    freeze(&mut contract);
+    // Only clear the contract wide maps so the accounts are left alone
+    contract.clear_storage(Some(0), contract_id(), None);
+    let contract = commit(contract);
+    assert!(contract.vendor_info_by_id.is_empty());
+
+    let stored = VendorInformation::new(&vendor_id());
+    assert!(stored.items.get("coffee").is_none());
+    assert!(stored.sales_by_item.get("coffee").is_none());
+}
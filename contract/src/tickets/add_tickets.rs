@@ -27,7 +27,8 @@ impl Contract {
     ///
     /// # Panics
     ///
-    /// Panics if the Keypom arguments are invalid or the drop ID does not exist.
+    /// Panics if the Keypom arguments are invalid, the drop ID does not exist, or if adding the
+    /// keys would exceed the ticket type's maximum supply.
     #[payable]
     pub fn add_tickets(&mut self, drop_id: DropId, key_data: Vec<KeyData>) {
         self.assert_no_freeze();
@@ -36,6 +37,18 @@ impl Contract {
         // More than 100 keys leads to promise rejection
         require!(key_data.len() < 100, "Maximum number of keys exceeded");
 
+        // Make sure the ticket type exists and has enough supply left for the new keys
+        let ticket_data = self
+            .ticket_data_by_id
+            .get(&drop_id)
+            .expect("No drop ID found");
+        let ticket_stats = self.ticket_stats_by_id.entry(drop_id.clone()).or_default();
+        ticket_stats.issued += key_data.len() as u64;
+        require!(
+            ticket_data.has_supply_for(ticket_stats.issued),
+            "Maximum supply for this ticket type exceeded"
+        );
+
         // Get the current account ID (which will be cloned later as needed)
         let current_account_id = env::current_account_id();
 
@@ -43,9 +56,12 @@ impl Contract {
         for key in key_data.iter() {
             let attendee_info = AttendeeTicketInformation {
                 drop_id: Some(drop_id.clone()),
-                has_scanned: false,
+                check_ins: Vec::new(),
+                reserved_account_id: None,
                 account_id: None,
                 metadata: key.metadata.clone(),
+                held_tokens: NearToken::from_yoctonear(0),
+                held_nft_ids: Vec::new(),
             };
 
             require!(
@@ -66,4 +82,63 @@ impl Contract {
 
         self.total_transactions += 1;
     }
+
+    /// Re-issues existing ticket keys with the current method names for their account type.
+    /// Keys are only granted their method names when they're added, so this needs to be run
+    /// after any upgrade that exposes new methods to ticket holders.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_index` - Index of the first ticket to refresh. Defaults to 0.
+    /// * `limit` - Maximum number of keys to refresh. Defaults to `MAX_KEYS_PER_REFRESH`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of tickets left to refresh after this batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin or the limit exceeds `MAX_KEYS_PER_REFRESH`.
+    pub fn refresh_ticket_keys(&mut self, from_index: Option<u64>, limit: Option<u64>) -> u64 {
+        self.assert_admin();
+
+        let start = from_index.unwrap_or(0);
+        let limit = limit.unwrap_or(MAX_KEYS_PER_REFRESH);
+        require!(
+            limit <= MAX_KEYS_PER_REFRESH,
+            "Maximum number of keys exceeded"
+        );
+
+        let current_account_id = env::current_account_id();
+        for (public_key, ticket) in self
+            .attendee_ticket_by_pk
+            .iter()
+            .skip(start as usize)
+            .take(limit as usize)
+        {
+            // Never touch the contract's own full access key
+            if *public_key == self.contract_key {
+                continue;
+            }
+
+            let method_names = ticket
+                .account_id
+                .as_ref()
+                .and_then(|account_id| self.account_details_by_id.get(account_id))
+                .and_then(|account_details| account_details.account_status.as_ref())
+                .map_or(ATTENDEE_KEY_METHOD_NAMES, AccountStatus::key_method_names);
+
+            // Deleting and re-adding in the same batch replaces the key's permissions
+            Promise::new(current_account_id.clone())
+                .delete_key(public_key.clone())
+                .add_access_key_allowance(
+                    public_key.clone(),
+                    Allowance::unlimited(),
+                    current_account_id.clone(),
+                    method_names.to_string(),
+                );
+        }
+
+        (self.attendee_ticket_by_pk.len() as u64).saturating_sub(start + limit)
+    }
 }
@@ -0,0 +1,274 @@
+use near_sdk::{Allowance, Promise};
+
+use crate::*;
+
+#[near]
+impl Contract {
+    /// Revokes a ticket so its key can no longer be used. The ticket information is removed and
+    /// the key's access key on the contract account is deleted. Any account created with the
+    /// ticket keeps its balances and claims.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key` - The public key of the ticket to be revoked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin or if no ticket exists for the key.
+    pub fn revoke_ticket(&mut self, public_key: PublicKey) {
+        self.assert_no_freeze();
+        self.assert_admin();
+        require!(
+            public_key != self.contract_key,
+            "Cannot revoke the contract key"
+        );
+
+        let attendee_info = self
+            .attendee_ticket_by_pk
+            .remove(&public_key)
+            .expect("No ticket information found for public key");
+
+        // Release any account name the ticket was holding
+        if let Some(reserved_account_id) = attendee_info.reserved_account_id.as_ref() {
+            self.reserved_account_names.remove(reserved_account_id);
+        }
+
+        // The ticket no longer counts towards its ticket type's stats
+        if let Some(stats) = attendee_info
+            .drop_id
+            .as_ref()
+            .and_then(|drop_id| self.ticket_stats_by_id.get_mut(drop_id))
+        {
+            stats.issued = stats.issued.saturating_sub(1);
+            stats.scanned = stats
+                .scanned
+                .saturating_sub(u64::from(attendee_info.has_scanned()));
+        }
+
+        Promise::new(env::current_account_id()).delete_key(public_key);
+
+        self.total_transactions += 1;
+    }
+
+    /// Moves a ticket to a new key, for example when an attendee loses their phone. The ticket
+    /// information is moved to the new key, the old access key on the contract account is deleted
+    /// and a new one with the same permissions is added. The attendee keeps their account,
+    /// balances and claims.
+    ///
+    /// Note that the full access key on the attendee's own account is not rotated since the
+    /// contract cannot manage keys on that account.
+    ///
+    /// # Arguments
+    ///
+    /// * `old_pk` - The public key currently associated with the ticket.
+    /// * `new_pk` - The public key the ticket should be moved to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin, if no ticket exists for the old key, or if the new
+    /// key is already in use.
+    pub fn rotate_ticket_key(&mut self, old_pk: PublicKey, new_pk: PublicKey) {
+        self.assert_no_freeze();
+        self.assert_admin();
+        require!(
+            old_pk != self.contract_key && new_pk != self.contract_key,
+            "Cannot rotate the contract key"
+        );
+
+        let attendee_info = self
+            .attendee_ticket_by_pk
+            .remove(&old_pk)
+            .expect("No ticket information found for public key");
+
+        // The new key should have the same permissions as the old one
+        let access_key_method_names = attendee_info
+            .account_id
+            .as_ref()
+            .and_then(|account_id| self.account_details_by_id.get(account_id))
+            .and_then(|details| details.account_status.as_ref())
+            .map_or(ATTENDEE_KEY_METHOD_NAMES, AccountStatus::key_method_names);
+
+        require!(
+            self.attendee_ticket_by_pk
+                .insert(new_pk.clone(), attendee_info)
+                .is_none(),
+            "Key already exists"
+        );
+
+        let current_account_id = env::current_account_id();
+        Promise::new(current_account_id.clone())
+            .delete_key(old_pk)
+            .add_access_key_allowance(
+                new_pk,
+                Allowance::unlimited(),
+                current_account_id,
+                access_key_method_names.to_string(),
+            );
+
+        self.total_transactions += 1;
+    }
+
+    /// Upgrades a ticket to a different ticket type, for example from Basic to VIP. If the new
+    /// ticket type starts with more tokens, the difference is minted to the attendee's account.
+    /// If the account has already been created, its account status and access key permissions are
+    /// updated to match the new ticket type as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key` - The public key of the ticket to be upgraded.
+    /// * `new_drop_id` - The drop ID of the ticket type to upgrade to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not an admin or ticket adder, if no ticket exists for the key, if
+    /// the new ticket type doesn't exist or is sold out, or if the ticket already has the new
+    /// ticket type.
+    pub fn upgrade_ticket(&mut self, public_key: PublicKey, new_drop_id: DropId) {
+        self.assert_no_freeze();
+        self.assert_ticket_adder();
+
+        let new_ticket_data = self
+            .ticket_data_by_id
+            .get(&new_drop_id)
+            .cloned()
+            .expect("No drop ID found");
+
+        let attendee_info = self
+            .attendee_ticket_by_pk
+            .get_mut(&public_key)
+            .expect("No ticket information found for public key");
+        require!(
+            attendee_info.drop_id.as_ref() != Some(&new_drop_id),
+            "Ticket already has this ticket type"
+        );
+        let old_drop_id = attendee_info.drop_id.replace(new_drop_id.clone());
+        let account_id = attendee_info.account_id.clone();
+        let has_scanned = attendee_info.has_scanned();
+
+        // Move the ticket over to the new ticket type's stats
+        if let Some(old_stats) = old_drop_id
+            .as_ref()
+            .and_then(|drop_id| self.ticket_stats_by_id.get_mut(drop_id))
+        {
+            old_stats.issued = old_stats.issued.saturating_sub(1);
+            old_stats.scanned = old_stats.scanned.saturating_sub(u64::from(has_scanned));
+            old_stats.accounts_created = old_stats
+                .accounts_created
+                .saturating_sub(u64::from(account_id.is_some()));
+        }
+        let new_stats = self.ticket_stats_by_id.entry(new_drop_id).or_default();
+        new_stats.issued += 1;
+        new_stats.scanned += u64::from(has_scanned);
+        new_stats.accounts_created += u64::from(account_id.is_some());
+        require!(
+            new_ticket_data.has_supply_for(new_stats.issued),
+            "Maximum supply for this ticket type exceeded"
+        );
+
+        // Accounts that haven't been created yet will get the new starting balance on creation
+        if let Some(account_id) = account_id {
+            let old_starting_balance = old_drop_id
+                .and_then(|drop_id| self.ticket_data_by_id.get(&drop_id))
+                .map_or(NearToken::from_yoctonear(0), |ticket_data| {
+                    ticket_data.starting_token_balance
+                });
+            let tokens_to_add = new_ticket_data
+                .starting_token_balance
+                .saturating_sub(old_starting_balance);
+
+            let account_details = self
+                .account_details_by_id
+                .get_mut(&account_id)
+                .expect("No account details found");
+            account_details.account_status = match new_ticket_data.account_type {
+                AccountStatus::Basic => None,
+                ref account_type => Some(account_type.clone()),
+            };
+
+            if !tokens_to_add.is_zero() {
+                self.internal_deposit_ft_mint(&account_id, tokens_to_add, None, false);
+            }
+
+            // Replace the access key so it has the permissions of the new ticket type
+            let current_account_id = env::current_account_id();
+            Promise::new(current_account_id.clone())
+                .delete_key(public_key.clone())
+                .add_access_key_allowance(
+                    public_key,
+                    Allowance::unlimited(),
+                    current_account_id,
+                    new_ticket_data.account_type.key_method_names().to_string(),
+                );
+        }
+
+        self.total_transactions += 1;
+    }
+
+    /// Hands an unscanned ticket over to someone else. Must be signed by the current ticket key.
+    /// The ticket information is moved to the new key and the contract access keys are swapped
+    /// so the old key can no longer be used. Any account name reserved by the ticket is released.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_public_key` - The public key of the new ticket holder.
+    /// * `new_metadata` - Optional encrypted metadata for the new ticket holder. The existing
+    /// metadata is cleared if none is passed in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no ticket exists for the signing key, if the ticket has already been scanned, or
+    /// if the new key is already in use.
+    pub fn transfer_ticket(&mut self, new_public_key: PublicKey, new_metadata: Option<String>) {
+        self.assert_no_freeze();
+        let ticket_pk = env::signer_account_pk();
+        require!(
+            new_public_key != self.contract_key,
+            "Cannot transfer to the contract key"
+        );
+
+        let mut attendee_info = self
+            .attendee_ticket_by_pk
+            .remove(&ticket_pk)
+            .expect("No ticket information found for public key");
+        require!(
+            !attendee_info.has_scanned() && attendee_info.account_id.is_none(),
+            "Ticket has already been scanned"
+        );
+        attendee_info.metadata = new_metadata;
+        // The account name belongs to the old holder so it's released for anyone to reserve
+        if let Some(reserved_account_id) = attendee_info.reserved_account_id.take() {
+            self.reserved_account_names.remove(&reserved_account_id);
+        }
+        let drop_id = attendee_info.drop_id.clone();
+
+        require!(
+            self.attendee_ticket_by_pk
+                .insert(new_public_key.clone(), attendee_info)
+                .is_none(),
+            "Key already exists"
+        );
+
+        let current_account_id = env::current_account_id();
+        Promise::new(current_account_id.clone())
+            .delete_key(ticket_pk.clone())
+            .add_access_key_allowance(
+                new_public_key.clone(),
+                Allowance::unlimited(),
+                current_account_id,
+                ATTENDEE_KEY_METHOD_NAMES.to_string(),
+            );
+
+        let ticket_transfer_log = EventLog {
+            standard: KEYPOM_STANDARD_NAME.to_string(),
+            version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
+            event: EventLogVariant::KeypomTicketTransfer(KeypomTicketTransferLog {
+                drop_id,
+                old_public_key: String::from(&ticket_pk),
+                new_public_key: String::from(&new_public_key),
+            }),
+        };
+        env::log_str(&ticket_transfer_log.to_string());
+
+        self.total_transactions += 1;
+    }
+}
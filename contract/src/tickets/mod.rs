@@ -1,2 +1,3 @@
 pub mod add_tickets;
+pub mod manage_tickets;
 pub mod views;
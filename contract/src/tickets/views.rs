@@ -1,5 +1,15 @@
 use crate::*;
 
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub struct ExtTicketTypeStats {
+    pub drop_id: DropId,
+    pub max_supply: Option<u64>,
+    pub issued: u64,
+    pub scanned: u64,
+    pub accounts_created: u64,
+}
+
 #[near]
 impl Contract {
     /// Query for the information of a specific attendee key.
@@ -37,6 +47,52 @@ impl Contract {
             .collect()
     }
 
+    /// Query for whether attendees can scan their own tickets instead of being scanned by
+    /// check-in staff.
+    pub fn is_self_check_in_allowed(&self) -> bool {
+        self.allow_self_check_in
+    }
+
+    /// Query for the number of tickets scanned in by staff at each gate or location.
+    ///
+    /// # Returns
+    ///
+    /// A vector of locations and the number of check-ins at each one.
+    pub fn get_check_ins_by_location(&self) -> Vec<(String, u64)> {
+        self.check_ins_by_location
+            .iter()
+            .map(|(location, count)| (location.clone(), *count))
+            .collect()
+    }
+
+    /// Query for the number of tickets scanned into each day or session.
+    ///
+    /// # Returns
+    ///
+    /// A vector of sessions and the number of attendees checked into each one.
+    pub fn get_attendance_by_session(&self) -> Vec<(String, u64)> {
+        self.attendance_by_session
+            .iter()
+            .map(|(session, count)| (session.clone(), *count))
+            .collect()
+    }
+
+    /// Query for the number of tickets scanned into a specific day or session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The day or session to get the attendance for.
+    ///
+    /// # Returns
+    ///
+    /// The number of attendees checked into the session.
+    pub fn get_session_attendance(&self, session: String) -> u64 {
+        self.attendance_by_session
+            .get(&session)
+            .copied()
+            .unwrap_or(0)
+    }
+
     /// Retrieves the ticket data for a given drop ID.
     ///
     /// # Arguments
@@ -57,6 +113,32 @@ impl Contract {
             .expect("No drop ID found")
     }
 
+    /// Query for the number of tickets issued, scanned and turned into accounts for every ticket
+    /// type.
+    ///
+    /// # Returns
+    ///
+    /// A vector of `ExtTicketTypeStats` containing the stats and maximum supply for each ticket type.
+    pub fn get_ticket_type_stats(&self) -> Vec<ExtTicketTypeStats> {
+        self.ticket_data_by_id
+            .iter()
+            .map(|(drop_id, ticket_data)| {
+                let stats = self
+                    .ticket_stats_by_id
+                    .get(drop_id)
+                    .cloned()
+                    .unwrap_or_default();
+                ExtTicketTypeStats {
+                    drop_id: drop_id.clone(),
+                    max_supply: ticket_data.max_supply,
+                    issued: stats.issued,
+                    scanned: stats.scanned,
+                    accounts_created: stats.accounts_created,
+                }
+            })
+            .collect()
+    }
+
     /// Updates the ticket data for a given drop ID.
     /// Can only be called by an admin.
     ///
@@ -67,9 +149,21 @@ impl Contract {
     ///
     /// # Panics
     ///
-    /// Panics if the caller is not an admin.
+    /// Panics if the caller is not an admin, if the maximum supply is lower than the number of
+    /// tickets already issued or if the wallet version doesn't exist.
     pub fn update_ticket_data(&mut self, drop_id: String, ticket_data: TicketType) {
         self.assert_admin();
+        if let Some(version) = ticket_data.wallet_version.as_ref() {
+            self.assert_wallet_exists(version);
+        }
+        let num_issued = self
+            .ticket_stats_by_id
+            .get(&drop_id)
+            .map_or(0, |stats| stats.issued);
+        require!(
+            ticket_data.has_supply_for(num_issued),
+            "Max supply cannot be lower than the number of tickets already issued"
+        );
         self.ticket_data_by_id.insert(drop_id, ticket_data);
     }
 }
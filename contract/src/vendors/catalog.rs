@@ -0,0 +1,61 @@
+use crate::*;
+
+#[near]
+impl Contract {
+    /// Allows a vendor to add items to their catalog. Items with an ID that is already in the
+    /// catalog replace the existing item while keeping its sales.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The items to add or replace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not a vendor or if an item has no name or a price of zero.
+    pub fn set_vendor_items(&mut self, items: Vec<VendorItem>) {
+        self.assert_no_freeze();
+        let vendor_id = self.assert_vendor();
+
+        let vendor_info = self
+            .vendor_info_by_id
+            .entry(vendor_id.clone())
+            .or_insert_with(|| VendorInformation::new(&vendor_id));
+        for item in items {
+            require!(!item.name.is_empty(), "Item name cannot be empty");
+            require!(
+                !item.price.is_zero(),
+                "Item price must be greater than zero"
+            );
+            vendor_info.items.insert(item.id.clone(), item);
+        }
+
+        self.total_transactions += 1;
+    }
+
+    /// Allows a vendor to remove items from their catalog. The sales of removed items are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `item_ids` - The IDs of the items to remove.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the caller is not a vendor or if an item is not in their catalog.
+    pub fn remove_vendor_items(&mut self, item_ids: Vec<VendorItemId>) {
+        self.assert_no_freeze();
+        let vendor_id = self.assert_vendor();
+
+        let vendor_info = self
+            .vendor_info_by_id
+            .get_mut(&vendor_id)
+            .expect("Vendor has no catalog");
+        for item_id in item_ids {
+            require!(
+                vendor_info.items.remove(&item_id).is_some(),
+                "Item not found in catalog"
+            );
+        }
+
+        self.total_transactions += 1;
+    }
+}
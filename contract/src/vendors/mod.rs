@@ -0,0 +1,6 @@
+pub mod catalog;
+pub mod models;
+pub mod purchase;
+pub mod views;
+
+pub use models::*;
@@ -0,0 +1,58 @@
+use crate::*;
+
+pub type VendorItemId = String;
+
+/// An item that a vendor sells for event tokens.
+#[derive(Clone)]
+#[near(serializers = [json, borsh])]
+pub struct VendorItem {
+    pub id: VendorItemId,
+    pub name: String,
+    pub price: NearToken,
+    // Number of items left to sell
+    pub stock: u64,
+    pub image: Option<String>,
+}
+
+/// Running totals of how much of an item has been sold.
+#[derive(Clone, Default)]
+#[near(serializers = [json, borsh])]
+pub struct VendorItemSales {
+    pub quantity_sold: u64,
+    pub revenue: NearToken,
+}
+
+/// A vendor's catalog along with its sales.
+#[near(serializers = [borsh])]
+pub struct VendorInformation {
+    pub items: IterableMap<VendorItemId, VendorItem>,
+    pub sales_by_item: IterableMap<VendorItemId, VendorItemSales>,
+    pub total_revenue: NearToken,
+    pub num_purchases: u64,
+}
+
+impl VendorInformation {
+    pub fn new(vendor_id: &AccountId) -> VendorInformation {
+        let items = IterableMap::new(StorageKeys::VendorItemsInner {
+            account_id_hash: hash_string(&vendor_id.to_string()),
+        });
+        let sales_by_item = IterableMap::new(StorageKeys::VendorSalesInner {
+            account_id_hash: hash_string(&vendor_id.to_string()),
+        });
+
+        VendorInformation {
+            items,
+            sales_by_item,
+            total_revenue: NearToken::from_yoctonear(0),
+            num_purchases: 0,
+        }
+    }
+}
+
+#[near(serializers = [json])]
+pub struct ExtVendorSales {
+    pub vendor_id: AccountId,
+    pub total_revenue: NearToken,
+    pub num_purchases: u64,
+    pub sales_by_item: Vec<(VendorItemId, VendorItemSales)>,
+}
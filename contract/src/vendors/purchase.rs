@@ -0,0 +1,105 @@
+use crate::*;
+
+#[near]
+impl Contract {
+    /// Allows an attendee to buy items from a vendor's catalog. The total price is transferred
+    /// from the attendee to the vendor and the vendor's stock is reduced.
+    ///
+    /// # Arguments
+    ///
+    /// * `vendor_id` - The vendor selling the items.
+    /// * `items` - The ID and quantity of each item to buy.
+    ///
+    /// # Returns
+    ///
+    /// Returns the total amount of tokens paid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the account being bought from is not a vendor, if an item is not in the catalog,
+    /// if there isn't enough stock or if the attendee doesn't have enough tokens.
+    pub fn purchase_items(
+        &mut self,
+        vendor_id: AccountId,
+        items: Vec<(VendorItemId, u64)>,
+    ) -> U128 {
+        self.assert_no_freeze();
+        let buyer_id = self.caller_id_by_signing_pk();
+        require!(!items.is_empty(), "No items to purchase");
+        require!(
+            self.account_details_by_id
+                .get(&vendor_id)
+                .and_then(|details| details.account_status.as_ref())
+                .is_some_and(|status| status.is_vendor()),
+            "Items can only be purchased from vendors"
+        );
+
+        let vendor_info = self
+            .vendor_info_by_id
+            .get_mut(&vendor_id)
+            .expect("Vendor has no catalog");
+
+        let mut total = NearToken::from_yoctonear(0);
+        let mut purchased_items = Vec::with_capacity(items.len());
+        for (item_id, quantity) in items {
+            require!(quantity > 0, "Quantity must be greater than zero");
+            let item = vendor_info
+                .items
+                .get_mut(&item_id)
+                .expect("Item not found in catalog");
+            require!(item.stock >= quantity, "Not enough stock");
+            item.stock -= quantity;
+
+            let cost = item
+                .price
+                .checked_mul(quantity as u128)
+                .expect("NearToken overflow");
+            total = total.checked_add(cost).expect("NearToken overflow");
+
+            let sales = vendor_info
+                .sales_by_item
+                .entry(item_id.clone())
+                .or_default();
+            sales.quantity_sold += quantity;
+            sales.revenue = sales.revenue.checked_add(cost).expect("NearToken overflow");
+
+            purchased_items.push(KeypomPurchasedItem {
+                item_id,
+                name: item.name.clone(),
+                quantity,
+                price: U128(item.price.as_yoctonear()),
+            });
+        }
+        vendor_info.total_revenue = vendor_info
+            .total_revenue
+            .checked_add(total)
+            .expect("NearToken overflow");
+        vendor_info.num_purchases += 1;
+
+        self.internal_ft_transfer(&buyer_id, &vendor_id, total, false, None);
+
+        self.add_transaction(TransactionType::Purchase {
+            buyer_id: buyer_id.clone(),
+            vendor_id: vendor_id.clone(),
+            amount: total,
+            timestamp: env::block_timestamp(),
+        });
+        self.total_transactions += 1;
+
+        env::log_str(
+            &EventLog {
+                standard: KEYPOM_STANDARD_NAME.to_string(),
+                version: KEYPOM_CONFERENCE_METADATA_SPEC.to_string(),
+                event: EventLogVariant::KeypomPurchase(KeypomPurchaseLog {
+                    buyer_id: buyer_id.to_string(),
+                    vendor_id: vendor_id.to_string(),
+                    items: purchased_items,
+                    total: U128(total.as_yoctonear()),
+                }),
+            }
+            .to_string(),
+        );
+
+        U128(total.as_yoctonear())
+    }
+}
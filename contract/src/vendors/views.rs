@@ -0,0 +1,57 @@
+use crate::*;
+
+#[near]
+impl Contract {
+    /// Query for every account that has a catalog.
+    pub fn get_vendors(&self) -> Vec<AccountId> {
+        self.vendor_info_by_id.keys().cloned().collect()
+    }
+
+    /// Query for the items a vendor sells.
+    ///
+    /// # Arguments
+    ///
+    /// * `vendor_id` - The vendor to query for.
+    ///
+    /// # Returns
+    ///
+    /// A vector of the items in the vendor's catalog, empty if the vendor has no catalog.
+    pub fn get_vendor_items(&self, vendor_id: AccountId) -> Vec<VendorItem> {
+        self.vendor_info_by_id
+            .get(&vendor_id)
+            .map(|vendor_info| vendor_info.items.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Query for a vendor's sales.
+    ///
+    /// # Arguments
+    ///
+    /// * `vendor_id` - The vendor to query for.
+    ///
+    /// # Returns
+    ///
+    /// The vendor's total revenue, number of purchases and the quantity sold and revenue of each
+    /// item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vendor has no catalog.
+    pub fn get_vendor_sales(&self, vendor_id: AccountId) -> ExtVendorSales {
+        let vendor_info = self
+            .vendor_info_by_id
+            .get(&vendor_id)
+            .expect("Vendor has no catalog");
+
+        ExtVendorSales {
+            vendor_id,
+            total_revenue: vendor_info.total_revenue,
+            num_purchases: vendor_info.num_purchases,
+            sales_by_item: vendor_info
+                .sales_by_item
+                .iter()
+                .map(|(item_id, sales)| (item_id.clone(), sales.clone()))
+                .collect(),
+        }
+    }
+}
@@ -7,11 +7,14 @@ export const cleanupContract = async ({
   factoryKey,
   factoryAccountId,
   networkId,
+  force = false,
 }: {
   near: any;
   factoryAccountId: string;
   factoryKey: string | undefined;
   networkId: string;
+  // Also clear accounts that haven't offboarded, burning their tokens
+  force?: boolean;
 }) => {
   if (factoryKey !== undefined) {
     let factoryKeyPair = KeyPair.fromString(factoryKey);
@@ -49,11 +52,12 @@ export const cleanupContract = async ({
     const result = await signerAccount.functionCall({
       contractId: factoryAccountId,
       methodName: "clear_storage",
-      args: { limit: 10, refund_account: signerAccount.accountId },
+      args: { limit: 10, refund_account: signerAccount.accountId, force },
       gas: "300000000000000",
     });
 
     // Get the number of accounts left from the function's return value
+    const previousAccountsLeft = accountsLeft;
     accountsLeft = parseInt(
       Buffer.from(result.status.SuccessValue, "base64").toString(),
     );
@@ -69,6 +73,14 @@ export const cleanupContract = async ({
     console.log(
       `Accounts left to clear: ${accountsLeft}, Bytes cleared in this batch: ${bytesCleared}`,
     );
+
+    // The remaining accounts haven't offboarded and are only cleared when forced
+    if (accountsLeft > 0 && accountsLeft === previousAccountsLeft) {
+      console.log(
+        `${accountsLeft} accounts haven't offboarded. Run the cleanup with force to clear them.`,
+      );
+      break;
+    }
   }
 
   // Delete function call access keys from the contract account
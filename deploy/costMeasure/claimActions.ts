@@ -5,7 +5,7 @@ import { sendTransaction } from "../utils";
 export async function claimDrop(
   signerAccount: Account,
   dropId: string,
-  signatureData: { signature: string; publicKey: string },
+  signatureData: { signature: string; publicKey: string; expiresAt?: string },
   factoryAccountId: string,
   scavengerId: string | null = null,
 ) {
@@ -17,6 +17,7 @@ export async function claimDrop(
       drop_id: dropId,
       scavenger_id: scavengerId,
       signature: signatureData.signature,
+      expires_at: signatureData.expiresAt,
     },
     deposit: "0",
     gas: "300000000000000",
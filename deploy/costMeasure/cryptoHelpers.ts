@@ -3,19 +3,36 @@ import nacl from "tweetnacl";
 import bs58 from "bs58";
 import { getPubFromSecret } from "@keypom/core";
 
-// Function to generate signature
-export function generateSignature(secretKeyStr: string, callerId: string) {
+// Version tag at the start of replay-resistant claim messages
+const CLAIM_MESSAGE_VERSION = "kpom-claim-v1";
+
+// How long a claim signature stays valid for (in nanoseconds)
+const CLAIM_SIGNATURE_TTL_NS = BigInt(5 * 60) * BigInt(1e9);
+
+// Function to generate signature. Pass in the drop ID and the caller's claim nonce to sign a
+// replay-resistant claim, otherwise the legacy message is signed.
+export function generateSignature(
+  secretKeyStr: string,
+  callerId: string,
+  claim?: { dropId: string; nonce: number },
+) {
   const secretKeyBase58 = secretKeyStr.replace("ed25519:", "");
   const secretKeyBytes = bs58.decode(secretKeyBase58);
   const keyPair = nacl.sign.keyPair.fromSecretKey(secretKeyBytes);
   const publicKeyBase58 = bs58.encode(keyPair.publicKey);
-  const message = `${callerId},${publicKeyBase58}`;
+  const expiresAt = claim
+    ? (BigInt(Date.now()) * BigInt(1e6) + CLAIM_SIGNATURE_TTL_NS).toString()
+    : undefined;
+  const message = claim
+    ? `${CLAIM_MESSAGE_VERSION},${claim.dropId},${callerId},${publicKeyBase58},${claim.nonce},${expiresAt}`
+    : `${callerId},${publicKeyBase58}`;
   const messageBytes = new TextEncoder().encode(message);
   const signature = nacl.sign.detached(messageBytes, keyPair.secretKey);
   const signatureBase64 = Buffer.from(signature).toString("base64");
   return {
     signature: signatureBase64,
     publicKey: `ed25519:${publicKeyBase58}`,
+    expiresAt,
   };
 }
 
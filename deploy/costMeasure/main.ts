@@ -318,8 +318,16 @@ async function main() {
       `Claim a ${type.toLowerCase()} drop`,
       async () => {
         const drop = drops[`${type.toLowerCase()}Drop`];
-        const signatureData = generateSignature(drop.privateKey, ticketUserId);
         const dropId = drop.dropId.split("%%")[2];
+        const nonce = await signerAccount.viewFunction(
+          factoryAccountId,
+          "get_claim_nonce",
+          { account_id: ticketUserId },
+        );
+        const signatureData = generateSignature(drop.privateKey, ticketUserId, {
+          dropId,
+          nonce,
+        });
         await claimDrop(signerAccount, dropId, signatureData, factoryAccountId);
       },
       type.toLowerCase() !== "nft",
@@ -350,8 +358,16 @@ async function main() {
       const scavengerHunt = drops["scavengerTokenHunt2"];
       const scavengerPieceKey = scavengerHunt.privateKey;
       const scavengerPieceId = getPublicKey(scavengerPieceKey);
-      const signatureData = generateSignature(scavengerPieceKey, ticketUserId);
       const dropId = scavengerHunt.dropId.split("%%")[3];
+      const nonce = await signerAccount.viewFunction(
+        factoryAccountId,
+        "get_claim_nonce",
+        { account_id: ticketUserId },
+      );
+      const signatureData = generateSignature(scavengerPieceKey, ticketUserId, {
+        dropId,
+        nonce,
+      });
       await claimDrop(
         signerAccount,
         dropId,
@@ -372,6 +388,7 @@ async function main() {
         factoryKey,
         factoryAccountId,
         networkId: GLOBAL_NETWORK,
+        force: true,
       });
       console.log("Cleanup Summary: ", cleanupSummary);
     },
@@ -4,6 +4,9 @@ import { MultichainDrop, NFTDrop, TokenDrop } from "./types";
 
 export type DropInfo = TokenDrop | NFTDrop | MultichainDrop;
 
+// Maximum number of drops the contract accepts in a single `create_drops` call
+const MAX_DROPS_PER_BATCH = 50;
+
 export const createDrops = async ({
   signerAccount,
   factoryAccountId,
@@ -14,15 +17,26 @@ export const createDrops = async ({
   drops: Array<DropInfo>;
 }) => {
   const dropIds: Array<string> = [];
+
+  // Build the creation args for every drop along with the secret keys that are only kept locally
+  const pendingDrops: Array<{
+    drop: DropInfo;
+    dropType: string;
+    dropSecretKey: string;
+    scavengerSecretKeys: Array<{
+      description: string;
+      secretKey: string;
+      publicKey: string;
+    }>;
+    args: any;
+  }> = [];
   for (const drop of drops) {
-    let res: any;
     let dropType: string;
-    let dropSecretKey: string;
 
     // Generate a key pair for the drop
     const dropKeyPair = KeyPair.fromRandom("ed25519");
     const dropPublicKey = dropKeyPair.getPublicKey().toString();
-    dropSecretKey = dropKeyPair.toString(); // Private key of the drop
+    const dropSecretKey = dropKeyPair.toString(); // Private key of the drop
 
     // Assign the public key to the drop data
     drop.drop_data.key = dropPublicKey;
@@ -66,75 +80,93 @@ export const createDrops = async ({
           description,
         }),
       ),
+      scavenger_milestones: drop.drop_data.scavenger_milestones?.map(
+        ({ pieces_found, token_amount }) => ({
+          pieces_found,
+          token_amount: utils.format.parseNearAmount(token_amount),
+        }),
+      ),
+      config: drop.drop_data.config,
     };
 
-    // Determine the drop type and send the appropriate transaction
+    // Determine the drop type and the reward for the drop
     if ((drop as TokenDrop).token_amount !== undefined) {
-      args.token_amount = utils.format.parseNearAmount(
-        (drop as TokenDrop).token_amount,
-      );
-      res = await sendTransaction({
-        signerAccount,
-        receiverId: factoryAccountId,
-        methodName: "create_token_drop",
-        args,
-        deposit: "0",
-        gas: "300000000000000",
-      });
+      args.reward = {
+        type: "Token",
+        token_amount: utils.format.parseNearAmount(
+          (drop as TokenDrop).token_amount,
+        ),
+      };
       dropType = "token";
     } else if ((drop as MultichainDrop).multichain_metadata !== undefined) {
-      args.multichain_metadata = (drop as MultichainDrop).multichain_metadata;
-      args.nft_metadata = (drop as MultichainDrop).nft_metadata;
-      res = await sendTransaction({
-        signerAccount,
-        receiverId: factoryAccountId,
-        methodName: "create_multichain_drop",
-        args,
-        deposit: "0",
-        gas: "300000000000000",
-      });
+      args.reward = {
+        type: "Multichain",
+        multichain_metadata: (drop as MultichainDrop).multichain_metadata,
+        nft_metadata: (drop as MultichainDrop).nft_metadata,
+      };
       dropType = "nft";
     } else {
-      args.nft_metadata = (drop as NFTDrop).nft_metadata;
-      res = await sendTransaction({
-        signerAccount,
-        receiverId: factoryAccountId,
-        methodName: "create_nft_drop",
-        args,
-        deposit: "0",
-        gas: "300000000000000",
-      });
+      args.reward = {
+        type: "Nft",
+        nft_metadata: (drop as NFTDrop).nft_metadata,
+      };
       dropType = "nft";
     }
 
+    pendingDrops.push({
+      drop,
+      dropType,
+      dropSecretKey,
+      scavengerSecretKeys,
+      args,
+    });
+  }
+
+  // Create the drops in batches since the contract caps the number of drops per call
+  for (let i = 0; i < pendingDrops.length; i += MAX_DROPS_PER_BATCH) {
+    const batch = pendingDrops.slice(i, i + MAX_DROPS_PER_BATCH);
+    const res: any = await sendTransaction({
+      signerAccount,
+      receiverId: factoryAccountId,
+      methodName: "create_drops",
+      args: { drops: batch.map(({ args }) => args) },
+      deposit: "0",
+      gas: "300000000000000",
+    });
+
     console.log("Response:", res);
     const status = res?.status;
-    if (status && status.SuccessValue) {
-      let dropId = Buffer.from(status.SuccessValue, "base64").toString("utf-8");
-      if (dropId.startsWith('"') && dropId.endsWith('"')) {
-        dropId = dropId.slice(1, -1);
-      }
+    if (!status || !status.SuccessValue) {
+      console.error("SuccessValue is not available");
+      continue;
+    }
+
+    const createdIds: Array<string> = JSON.parse(
+      Buffer.from(status.SuccessValue, "base64").toString("utf-8"),
+    );
+    batch.forEach(
+      ({ drop, dropType, dropSecretKey, scavengerSecretKeys }, index) => {
+        const dropId = createdIds[index];
 
-      // Handle scavenger hunt data if present
-      if (drop.drop_data.scavenger_hunt) {
-        let pieceNum = 1;
-        for (const piece of scavengerSecretKeys) {
-          // Write a CSV entry for each scavenger piece with the secret key
+        // Handle scavenger hunt data if present
+        if (drop.drop_data.scavenger_hunt) {
+          let pieceNum = 1;
+          for (const piece of scavengerSecretKeys) {
+            // Write a CSV entry for each scavenger piece with the secret key
+            dropIds.push(
+              `"${drop.drop_data.name} - Piece ${pieceNum}",${dropType}%%piece%%${piece.secretKey}%%${dropId}`,
+            );
+            pieceNum++;
+          }
+        } else {
+          // Handle regular token or NFT drop
+          // Write the secret key of the drop
           dropIds.push(
-            `"${drop.drop_data.name} - Piece ${pieceNum}",${dropType}%%piece%%${piece.secretKey}%%${dropId}`,
+            `"${drop.drop_data.name}",${dropType}%%${dropSecretKey}%%${dropId}`,
           );
-          pieceNum++;
         }
-      } else {
-        // Handle regular token or NFT drop
-        // Write the secret key of the drop
-        dropIds.push(
-          `"${drop.drop_data.name}",${dropType}%%${dropSecretKey}%%${dropId}`,
-        );
-      }
-    } else {
-      console.error("SuccessValue is not available");
-    }
+      },
+    );
   }
 
   return dropIds;
@@ -20,6 +20,12 @@ export async function deployFactory({
       startingNearBalance: string;
       startingTokenBalance: string;
       accountType: string;
+      // Days or sessions the ticket grants access to. Leave out to grant access to all of them
+      sessions?: string[];
+      // Maximum number of tickets that can be issued. Leave out for no limit
+      maxSupply?: number;
+      // Wallet version deployed to created accounts. Leave out to use the default wallet
+      walletVersion?: string;
     }
   >;
   factoryAccountId: string;
@@ -34,6 +40,9 @@ export async function deployFactory({
         value.startingTokenBalance,
       ),
       account_type: value.accountType,
+      sessions: value.sessions,
+      max_supply: value.maxSupply,
+      wallet_version: value.walletVersion,
     };
   }
 
@@ -7,6 +7,24 @@ export interface DropData {
   image: string;
   key?: string;
   scavenger_hunt?: ScavengerHuntPiece[];
+  scavenger_milestones?: ScavengerMilestone[];
+  config?: DropConfig;
+}
+
+// Tokens paid out once a claimer has found the given number of scavenger pieces
+export interface ScavengerMilestone {
+  pieces_found: number;
+  token_amount: string;
+}
+
+// Claim limits for a drop. Drops created by sponsors that pay out tokens must set max_claims.
+export interface DropConfig {
+  max_claims?: number;
+  starts_at?: number;
+  ends_at?: number;
+  paused?: boolean;
+  ordered_scavenger_hunt?: boolean;
+  legacy_claim_signatures?: boolean;
 }
 
 export interface NFTMetadata {